@@ -0,0 +1,175 @@
+//! Expected answers, read from `answers.toml` at the crate root.
+//!
+//! Only the small part of TOML the file needs is understood: `[dayN]` tables with
+//! `sample_part1`, `sample_part2`, `input_part1` and `input_part2` keys, whose values are
+//! integers, basic strings or `'''` multi-line literal strings.
+
+use std::{collections::HashMap, fmt::Display, fs::read_to_string, path::Path};
+
+use crate::{input_lines, AdventResult, InputLine, Part};
+
+/// Outcome of checking an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no expected answer to check against.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    expected: HashMap<(u8, bool, Part), String>,
+}
+
+impl Answers {
+    /// Loads `answers.toml`, no file means every answer is unknown.
+    pub fn load() -> AdventResult<Self> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> AdventResult<Self> {
+        let mut answers = Self::default();
+        let mut day = 0;
+        let mut lines = input_lines(0, text);
+        while let Some(mut line) = lines.next() {
+            line.day = day;
+            let clean_buffer = line.text.trim();
+            if clean_buffer.is_empty() || clean_buffer.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = clean_buffer.strip_prefix('[') {
+                let name = table.strip_suffix(']').unwrap_or(table);
+                let number = name
+                    .strip_prefix("day")
+                    .ok_or_else(|| line.error(name, "dayN"))?;
+                day = line.parse(number, "a day number")?;
+                continue;
+            }
+            let (key, value) = clean_buffer
+                .split_once('=')
+                .ok_or_else(|| line.error_at_end("'='"))?;
+            let (key, value) = (key.trim(), value.trim());
+            let (use_sample, part) = match key {
+                "sample_part1" => (true, Part::One),
+                "sample_part2" => (true, Part::Two),
+                "input_part1" => (false, Part::One),
+                "input_part2" => (false, Part::Two),
+                _ => return Err(line.error(key, "sample_partN or input_partN")),
+            };
+            let value = if value == "'''" {
+                multi_line_string(&line, &mut lines)?
+            } else if let Some(string) = value.strip_prefix('"') {
+                string
+                    .strip_suffix('"')
+                    .ok_or_else(|| line.error_at_end("'\"'"))?
+                    .replace("\\n", "\n")
+            } else {
+                line.parse::<i128>(value, "an integer or a string")?
+                    .to_string()
+            };
+            answers.expected.insert((day, use_sample, part), value);
+        }
+        Ok(answers)
+    }
+
+    pub fn expected(&self, day: u8, use_sample: bool, part: Part) -> Option<&str> {
+        self.expected
+            .get(&(day, use_sample, part))
+            .map(String::as_str)
+    }
+
+    /// Checks the answer, ignoring trailing spaces on each line and blank lines around it.
+    pub fn verify(&self, day: u8, use_sample: bool, part: Part, answer: &str) -> Verdict {
+        match self.expected(day, use_sample, part) {
+            Some(expected) if normalize(expected) == normalize(answer) => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// Reads the lines up to the closing `'''`, which must be on its own line.
+fn multi_line_string<'a>(
+    start: &InputLine<'a>,
+    lines: &mut impl Iterator<Item = InputLine<'a>>,
+) -> AdventResult<String> {
+    let mut value = vec![];
+    for line in lines {
+        if line.text.trim() == "'''" {
+            return Ok(value.join("\n"));
+        }
+        value.push(line.text);
+    }
+    Err(start.error_at_end("a closing '''"))
+}
+
+fn normalize(answer: &str) -> String {
+    answer
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdventError;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(
+            "# Comment\n[day1]\nsample_part1 = 7\ninput_part2 = \"abc\"\n\n[day13]\nsample_part2 = '''\n##\n# \n'''\n",
+        )
+        .unwrap();
+        assert_eq!(answers.expected(1, true, Part::One), Some("7"));
+        assert_eq!(answers.expected(1, false, Part::Two), Some("abc"));
+        assert_eq!(answers.expected(1, true, Part::Two), None);
+        assert_eq!(
+            answers.verify(13, true, Part::Two, "\n##\n#"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.verify(1, true, Part::One, "8"),
+            Verdict::Fail {
+                expected: "7".to_owned()
+            }
+        );
+        assert_eq!(answers.verify(2, true, Part::One, "8"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Answers::parse("[day1]\nsample_part3 = 1\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                day: 1,
+                line: 2,
+                ..
+            }
+        ));
+        assert!(Answers::parse("[day1]\ninput_part1 = '''\n1\n").is_err());
+    }
+}
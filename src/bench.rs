@@ -0,0 +1,219 @@
+//! Statistical benchmarks of the solutions and their variants.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{read_to_string, write},
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{input_lines, AdventResult, Part, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Runs before measuring, to warm up caches and the branch predictor.
+    pub warmup: usize,
+    /// Measured runs.
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+/// Summary of the measured runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len().max(1) as f64;
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (count - 1.0).max(1.0);
+        let median = match sorted.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            n => sorted[n / 2],
+        };
+        Self {
+            min: sorted.first().copied().unwrap_or_default(),
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>12?} median {:>12?} mean {:>12?} std dev {:>12?}",
+            self.min, self.median, self.mean, self.std_dev
+        )
+    }
+}
+
+/// Runs `f` the configured number of times, the result is kept from being optimised away.
+pub fn measure<T>(config: BenchConfig, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(f());
+    }
+    let samples: Vec<Duration> = (0..config.iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// Stable name used in the baseline, e.g. `day6.part1` or `day6.part1.growth_lazy`.
+    pub name: String,
+    pub label: String,
+    pub stats: Stats,
+}
+
+/// Benchmarks the parse, both parts and every variant of the solution.
+pub fn bench_solution<S: Solution>(
+    input: &str,
+    config: BenchConfig,
+) -> AdventResult<Vec<BenchResult>> {
+    let data = S::parse(input)?;
+    let mut results = vec![BenchResult {
+        name: format!("day{}.parse", S::DAY),
+        label: "Parse".to_owned(),
+        stats: measure(config, || S::parse(input)),
+    }];
+    for part in [Part::One, Part::Two] {
+        S::solve(&data, part)?;
+        results.push(BenchResult {
+            name: format!("day{}.{}", S::DAY, part_key(part)),
+            label: part.to_string(),
+            stats: measure(config, || S::solve(&data, part)),
+        });
+        for variant in S::variants().into_iter().filter(|v| v.part == part) {
+            (variant.solve)(&data)?;
+            results.push(BenchResult {
+                name: format!("day{}.{}.{}", S::DAY, part_key(part), variant.name),
+                label: format!("{} ({})", part, variant.name),
+                stats: measure(config, || (variant.solve)(&data)),
+            });
+        }
+    }
+    Ok(results)
+}
+
+fn part_key(part: Part) -> &'static str {
+    match part {
+        Part::One => "part1",
+        Part::Two => "part2",
+    }
+}
+
+/// Medians of a previous run, to spot regressions.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Baseline {
+    medians: HashMap<String, Duration>,
+}
+
+impl Baseline {
+    /// Reads a baseline, one `name median_ns` pair per line.
+    pub fn load(path: &Path) -> AdventResult<Self> {
+        Self::parse(&read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> AdventResult<Self> {
+        let mut medians = HashMap::new();
+        for line in input_lines(0, text) {
+            let clean_buffer = line.text.trim();
+            if clean_buffer.is_empty() || clean_buffer.starts_with('#') {
+                continue;
+            }
+            let (name, median) = clean_buffer
+                .split_once(' ')
+                .ok_or_else(|| line.error_at_end("a median in nanoseconds"))?;
+            let median = line.parse::<u64>(median, "a median in nanoseconds")?;
+            medians.insert(name.to_owned(), Duration::from_nanos(median));
+        }
+        Ok(Self { medians })
+    }
+
+    pub fn from_results(results: &[BenchResult]) -> Self {
+        Self {
+            medians: results
+                .iter()
+                .map(|r| (r.name.clone(), r.stats.median))
+                .collect(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> AdventResult<()> {
+        let mut names: Vec<_> = self.medians.keys().collect();
+        names.sort();
+        let mut text = String::from("# name median_ns\n");
+        for name in names {
+            text += &format!("{} {}\n", name, self.medians[name].as_nanos());
+        }
+        Ok(write(path, text)?)
+    }
+
+    /// Relative change of the median against the baseline, `0.1` being 10% slower.
+    pub fn change(&self, result: &BenchResult) -> Option<f64> {
+        let baseline = self.medians.get(&result.name)?.as_secs_f64();
+        (baseline > 0.0).then(|| result.stats.median.as_secs_f64() / baseline - 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        // Sample standard deviation of 1, 2, 3 and 4 is sqrt(5 / 3)
+        assert_eq!(stats.std_dev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn baseline_regressions() {
+        let baseline = Baseline::parse("# name median_ns\nday1.part1 1000\n").unwrap();
+        let result = |name: &str, nanos| BenchResult {
+            name: name.to_owned(),
+            label: String::new(),
+            stats: Stats::from_samples(&[Duration::from_nanos(nanos)]),
+        };
+        let change = baseline.change(&result("day1.part1", 1500)).unwrap();
+        assert!((change - 0.5).abs() < 1e-9);
+        assert_eq!(baseline.change(&result("day1.part2", 1500)), None);
+        let saved = Baseline::from_results(&[result("day1.part1", 1000)]);
+        assert_eq!(saved, baseline);
+        assert!(Baseline::parse("day1.part1 fast\n").is_err());
+    }
+}
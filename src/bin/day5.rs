@@ -1,240 +1,46 @@
-use std::{collections::HashMap, env::args, fmt::Display};
+//! Also takes `--export <path>`, writing the vent map of part 2 to `<path>.pgm`, `<path>.ppm`
+//! and `<path>.svg`.
 
-use advent::{read_input, timed_run, AdventResult};
+use std::{fs::File, io::BufWriter};
 
-/// Simple point, since it's just u32, we are going to allow Copy and Clone.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: u32,
-    y: u32,
-}
-
-/// Just to get beauty prints.
-impl Display for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{},{}", self.x, self.y)
-    }
-}
-
-/// Line segment
-#[derive(Debug, Default)]
-struct LineSegment {
-    p1: Point,
-    p2: Point,
-}
-
-/// Just to get beauty prints.
-impl Display for LineSegment {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} -> {}", self.p1, self.p2)
-    }
-}
-
-impl LineSegment {
-    fn new(p1: Point, p2: Point) -> Self {
-        Self { p1, p2 }
-    }
-
-    /// This uses the full algorithm for Bresenham lines
-    ///
-    /// [Wikipedia](https://en.wikipedia.org/wiki/Bresenham's_line_algorithm)
-    fn bresenham_line_points(&self) -> Vec<Point> {
-        let dx = (self.p2.x as i64 - self.p1.x as i64).abs();
-        let dy = -(self.p2.y as i64 - self.p1.y as i64).abs();
-        let mut err = dx + dy;
-        let mut x = self.p1.x;
-        let mut y = self.p1.y;
-
-        let mut points = vec![];
-        loop {
-            points.push(Point { x, y });
-            if x == self.p2.x && y == self.p2.y {
-                break;
-            }
-            let e2 = 2 * err;
-            if e2 >= dy {
-                err += dy;
-                if self.p1.x < self.p2.x {
-                    x += 1;
-                } else {
-                    x -= 1;
-                }
-            }
-            if e2 <= dx {
-                err += dx;
-                if self.p1.y < self.p2.y {
-                    y += 1;
-                } else {
-                    y -= 1;
-                }
-            }
-        }
-        points
-    }
-}
-
-/// Our grid where we store the lines and overlap information.
-#[derive(Debug, Default)]
-struct Grid {
-    lines: Vec<LineSegment>,
-    size: (u32, u32),
-    accumulator: HashMap<Point, u32>,
-}
-
-impl Grid {
-    fn insert_line_orthogonal_trace(&mut self, line: LineSegment) {
-        self.size.0 = self.size.0.max(line.p1.x).max(line.p2.x);
-        self.size.1 = self.size.1.max(line.p1.y).max(line.p2.y);
-        self.trace_orthogonal_lines(&line);
-        self.lines.push(line);
-    }
-
-    fn insert_line(&mut self, line: LineSegment) {
-        self.size.0 = self.size.0.max(line.p1.x).max(line.p2.x);
-        self.size.1 = self.size.1.max(line.p1.y).max(line.p2.y);
-        self.trace_lines(&line);
-        self.lines.push(line);
-    }
-
-    fn trace_orthogonal_lines(&mut self, line: &LineSegment) {
-        if line.p1.x == line.p2.x {
-            let x = line.p1.x;
-            let ymin = line.p1.y.min(line.p2.y);
-            let ymax = line.p1.y.max(line.p2.y);
-            for y in ymin..=ymax {
-                self.accumulator
-                    .entry(Point { x, y })
-                    .and_modify(|v| *v += 1)
-                    .or_insert(1);
-            }
-        }
-        if line.p1.y == line.p2.y {
-            let y = line.p1.y;
-            let x_min = line.p1.x.min(line.p2.x);
-            let x_max = line.p1.x.max(line.p2.x);
-            for x in x_min..=x_max {
-                self.accumulator
-                    .entry(Point { x, y })
-                    .and_modify(|v| *v += 1)
-                    .or_insert(1);
-            }
-        }
-    }
+use advent::{
+    cli::{day_run, Options},
+    days::day5::{build_grid, Day5, SegmentFilter},
+    AdventError, AdventResult, InputSource, Solution,
+};
 
-    fn trace_lines(&mut self, line: &LineSegment) {
-        let points = line.bresenham_line_points();
-        for point in points {
-            self.accumulator
-                .entry(point)
-                .and_modify(|v| *v += 1)
-                .or_insert(1);
-        }
-    }
-
-    fn overlaps(&self) -> u32 {
-        self.accumulator.iter().filter(|(_, &v)| v >= 2).count() as u32
-    }
-
-    fn draw_lines_data(&self) {
-        for line in &self.lines {
-            print!("{} [", &line);
-            for point in &line.bresenham_line_points() {
-                print!("({}),", point);
-            }
-            println!("]");
-        }
-    }
-
-    fn draw_grid(&self) {
-        for y in 0..=self.size.1 {
-            for x in 0..=self.size.0 {
-                let p = Point { x, y };
-                let v = if let Some((_, a)) = self.accumulator.get_key_value(&p) {
-                    format!("{}", a)
-                } else {
-                    ".".to_owned()
-                };
-                print!("{}", v);
-            }
-            println!();
-        }
-    }
-}
-
-/// Parses the lines and adds them to the grid.
-///
-/// If `orthogonal` is true, only horizontal or vertical lines are check for overlaps.
-/// This is for the first part.
-fn parse_input(input: &str, orthogonal: bool) -> Grid {
-    let mut grid = Grid::default();
-
-    for line in input.lines() {
-        let clean_buffer = line.trim();
-        let points_str = clean_buffer.split("->");
-        let points = points_str
-            .map(|p| {
-                p.trim()
-                    .split(',')
-                    .filter_map(|n| n.parse::<u32>().ok())
-                    .collect::<Vec<u32>>()
-            })
-            .filter_map(|p| {
-                if p.len() != 2 {
-                    None
-                } else {
-                    Some(Point { x: p[0], y: p[1] })
-                }
-            })
-            .collect::<Vec<Point>>();
-        if points.len() != 2 {
-            println!("Invalid line '{}'.", &clean_buffer);
-            continue;
-        }
-        let line = LineSegment::new(points[0], points[1]);
-        if orthogonal {
-            grid.insert_line_orthogonal_trace(line);
-        } else {
-            grid.insert_line(line);
-        }
-    }
-    grid
-}
-
-/// To get a nice diagram for the sample data use the --sample argument
 fn main() -> AdventResult<()> {
-    let use_sample = args().any(|arg| arg == "--sample");
-    let input = read_input(5, use_sample)?;
-    let grid = timed_run!("Part 1", parse_input(&input, true));
-    println!("Grid size: {}x{}", &grid.size.0, &grid.size.1);
-    if use_sample {
-        grid.draw_lines_data();
-        grid.draw_grid();
-    }
-    println!("Overlaps: {}", grid.overlaps());
-    let grid = timed_run!("Part 2", parse_input(&input, false));
-    if use_sample {
-        grid.draw_lines_data();
-        grid.draw_grid();
-    }
-    println!("Overlaps: {}", grid.overlaps());
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn validate_overlaps_part1() {
-        let input = read_input(5, true).expect("Error reading input");
-        let grid = parse_input(&input, true);
-        assert_eq!(grid.overlaps(), 5);
-    }
-
-    #[test]
-    fn validate_overlaps_part2() {
-        let input = read_input(5, true).expect("Error reading input");
-        let grid = parse_input(&input, false);
-        assert_eq!(grid.overlaps(), 12);
-    }
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let export = match args.iter().position(|arg| arg == "--export") {
+        Some(index) if index + 1 < args.len() => {
+            let path = args.remove(index + 1);
+            args.remove(index);
+            Some(path)
+        }
+        Some(_) => {
+            return Err(AdventError::InvalidArgument(
+                "--export expects a path".to_owned(),
+            ))
+        }
+        None => None,
+    };
+    let mut options = Options::parse(args)?;
+    if let Some(path) = export {
+        // Read once, the standard input can't be read again by the run
+        let input = options.input.read(Day5::DAY, options.use_sample)?;
+        let grid = build_grid(&Day5::parse(&input)?, SegmentFilter::Diagonal);
+        let file = |extension| -> AdventResult<_> {
+            Ok(BufWriter::new(File::create(format!(
+                "{}.{}",
+                path, extension
+            ))?))
+        };
+        grid.write_pgm(file("pgm")?)?;
+        grid.write_ppm(file("ppm")?)?;
+        grid.write_svg(file("svg")?, 2)?;
+        if !options.input.is_per_day() {
+            options.input = InputSource::Text(input);
+        }
+    }
+    day_run(Day5::DAY, &options)
 }
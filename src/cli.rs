@@ -0,0 +1,396 @@
+//! Command line handling shared by the `advent` runner and the per day binaries.
+
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use crate::{
+    bench::{Baseline, BenchConfig},
+    days,
+    report::{csv, json},
+    set_verbosity, AdventError, AdventResult, Answers, DayReport, Format, InputSource, Part,
+    Timing, Verbosity, Verdict,
+};
+
+const USAGE: &str = "Usage:
+    advent run <day> [--part 1|2] [--sample] [--input <path>|-|--embedded]
+               [--format text|json|csv] [-v|-vv]
+    advent all [--sample] [--embedded] [--format text|json|csv] [-v|-vv]
+    advent bench [<day>] [--iterations N] [--warmup N] [--baseline <path>]
+                 [--save-baseline <path>] [--threshold <percent>] [--sample] [-v|-vv]";
+
+/// Flags shared by every command.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Only run this part, both when `None`.
+    pub part: Option<Part>,
+    pub use_sample: bool,
+    pub input: InputSource,
+    pub format: Format,
+    /// What the solutions print on stderr while running.
+    pub verbosity: Verbosity,
+}
+
+impl Options {
+    fn parse_flag<I: Iterator<Item = String>>(
+        &mut self,
+        flag: &str,
+        args: &mut I,
+    ) -> AdventResult<()> {
+        match flag {
+            "--sample" => self.use_sample = true,
+            "--embedded" => self.input = InputSource::Embedded,
+            "-v" => self.verbosity = Verbosity::Info,
+            "-vv" => self.verbosity = Verbosity::Trace,
+            "--format" => {
+                self.format = args
+                    .next()
+                    .ok_or_else(|| {
+                        AdventError::InvalidArgument(
+                            "--format expects json, csv or text".to_owned(),
+                        )
+                    })?
+                    .parse()?;
+            }
+            "-" => self.input = InputSource::Stdin,
+            "--input" => {
+                let path = args.next().ok_or_else(|| {
+                    AdventError::InvalidArgument("--input expects a path or -".to_owned())
+                })?;
+                self.input = InputSource::from_arg(&path);
+            }
+            "--part" => {
+                self.part = match args.next().as_deref() {
+                    Some("1") => Some(Part::One),
+                    Some("2") => Some(Part::Two),
+                    _ => {
+                        return Err(AdventError::InvalidArgument(
+                            "--part expects 1 or 2".to_owned(),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(AdventError::InvalidArgument(format!(
+                    "unknown flag '{}'",
+                    flag
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the flags, anything that is not a flag is an error.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> AdventResult<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            options.parse_flag(&arg, &mut args)?;
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Runs a single day.
+    Run { day: u8, options: Options },
+    /// Runs every day in order.
+    All { options: Options },
+    /// Benchmarks a day, or every day.
+    Bench {
+        day: Option<u8>,
+        options: Options,
+        bench: BenchOptions,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub config: BenchConfig,
+    /// Baseline to compare the medians with.
+    pub baseline: Option<PathBuf>,
+    /// Where to save the medians as the next baseline.
+    pub save_baseline: Option<PathBuf>,
+    /// How much slower than the baseline, in percent, is a regression.
+    pub threshold: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            config: BenchConfig::default(),
+            baseline: None,
+            save_baseline: None,
+            threshold: 10,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Parses the bench flags, leaving the rest to [`Options`].
+    fn parse<I: Iterator<Item = String>>(args: I) -> AdventResult<(Self, Options)> {
+        let mut bench = Self::default();
+        let mut options = Options::default();
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next().ok_or_else(|| {
+                    AdventError::InvalidArgument(format!("{} expects a value", name))
+                })
+            };
+            match arg.as_str() {
+                "--iterations" => bench.config.iterations = value(&arg)?.parse()?,
+                "--warmup" => bench.config.warmup = value(&arg)?.parse()?,
+                "--threshold" => bench.threshold = value(&arg)?.parse()?,
+                "--baseline" => bench.baseline = Some(PathBuf::from(value(&arg)?)),
+                "--save-baseline" => bench.save_baseline = Some(PathBuf::from(value(&arg)?)),
+                _ => options.parse_flag(&arg, &mut args)?,
+            }
+        }
+        Ok((bench, options))
+    }
+
+    fn execute(&self, days: RangeInclusive<u8>, options: &Options) -> AdventResult<()> {
+        set_verbosity(options.verbosity);
+        let baseline = self.baseline.as_deref().map(Baseline::load).transpose()?;
+        let mut results = vec![];
+        let mut regressions = 0;
+        for day in days {
+            println!(
+                "Day {} ({} iterations after {} warmup)",
+                day, self.config.iterations, self.config.warmup
+            );
+            let input = options.input.read(day, options.use_sample)?;
+            for result in days::bench_day(day, &input, self.config)? {
+                print!("  {:<20} {}", result.label, result.stats);
+                if let Some(change) = baseline.as_ref().and_then(|b| b.change(&result)) {
+                    print!(" {:+7.1}%", change * 100.0);
+                    if change * 100.0 > self.threshold as f64 {
+                        print!(" REGRESSION");
+                        regressions += 1;
+                    }
+                }
+                println!();
+                results.push(result);
+            }
+        }
+        if baseline.is_some() {
+            println!("{} regression(s) against the baseline", regressions);
+        }
+        if let Some(path) = &self.save_baseline {
+            Baseline::from_results(&results).save(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Command {
+    /// Parses the arguments, without the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> AdventResult<Self> {
+        let mut args = args.into_iter();
+        match args.next().as_deref() {
+            Some("run") => {
+                let day = args
+                    .next()
+                    .ok_or_else(|| AdventError::InvalidArgument("missing day".to_owned()))?
+                    .parse::<u8>()?;
+                Ok(Command::Run {
+                    day,
+                    options: Options::parse(args)?,
+                })
+            }
+            Some("all") => Ok(Command::All {
+                options: Options::parse(args)?,
+            }),
+            Some("bench") => {
+                let mut args = args.peekable();
+                let day = match args.next_if(|arg| !arg.starts_with('-')) {
+                    Some(day) => Some(day.parse::<u8>()?),
+                    None => None,
+                };
+                let (bench, options) = BenchOptions::parse(args)?;
+                Ok(Command::Bench {
+                    day,
+                    options,
+                    bench,
+                })
+            }
+            Some(other) => Err(AdventError::InvalidArgument(format!(
+                "unknown command '{}'\n{}",
+                other, USAGE
+            ))),
+            None => Err(AdventError::InvalidArgument(USAGE.to_owned())),
+        }
+    }
+
+    /// Runs the command, returning the report of every day that was run.
+    pub fn execute(&self) -> AdventResult<Vec<DayReport>> {
+        let answers = Answers::load()?;
+        match self {
+            Command::Run { day, options } => run_days(*day..=*day, options, &answers),
+            Command::Bench {
+                day,
+                options,
+                bench,
+            } => {
+                let days = match day {
+                    Some(day) => *day..=*day,
+                    None => {
+                        check_per_day(options)?;
+                        days::DAYS
+                    }
+                };
+                bench.execute(days, options)?;
+                Ok(vec![])
+            }
+            Command::All { options } => {
+                check_per_day(options)?;
+                run_days(days::DAYS, options, &answers)
+            }
+        }
+    }
+}
+
+/// Runs the days and prints their reports in the requested format.
+fn run_days(
+    range: RangeInclusive<u8>,
+    options: &Options,
+    answers: &Answers,
+) -> AdventResult<Vec<DayReport>> {
+    set_verbosity(options.verbosity);
+    let several = range.start() != range.end();
+    let mut reports = vec![];
+    for day in range {
+        let report = days::run_day(day, options, answers)?;
+        if options.format == Format::Text {
+            print!("{}", report.text());
+            if several {
+                println!();
+            }
+        }
+        reports.push(report);
+    }
+    match options.format {
+        Format::Text if several => {
+            println!(
+                "{}",
+                Timing::total("All days", reports.iter().map(|r| &r.timing))
+            );
+            println!(
+                "Answers: {} pass, {} fail, {} unknown",
+                count(&reports, |v| *v == Verdict::Pass),
+                count(&reports, |v| matches!(v, Verdict::Fail { .. })),
+                count(&reports, |v| *v == Verdict::Unknown),
+            );
+        }
+        Format::Text => (),
+        Format::Json => println!("{}", json(&reports)),
+        Format::Csv => print!("{}", csv(&reports)),
+    }
+    Ok(reports)
+}
+
+/// Running several days needs an input source with every day.
+fn check_per_day(options: &Options) -> AdventResult<()> {
+    if options.input.is_per_day() {
+        Ok(())
+    } else {
+        Err(AdventError::InvalidArgument(
+            "can't read every day from a single input".to_owned(),
+        ))
+    }
+}
+
+fn count(reports: &[DayReport], verdict: fn(&Verdict) -> bool) -> usize {
+    reports.iter().map(|r| r.count(verdict)).sum()
+}
+
+/// Fails when any answer didn't match the expected one.
+fn check_answers(reports: &[DayReport]) -> AdventResult<()> {
+    match count(reports, |v| matches!(v, Verdict::Fail { .. })) {
+        0 => Ok(()),
+        failed => Err(AdventError::WrongAnswers(failed)),
+    }
+}
+
+/// Entry point of the `advent` binary.
+pub fn main() -> AdventResult<()> {
+    let reports = Command::parse(std::env::args().skip(1))?.execute()?;
+    check_answers(&reports)
+}
+
+/// Entry point of the `dayN` binaries, they only take the flags.
+pub fn day_main(day: u8) -> AdventResult<()> {
+    day_run(day, &Options::parse(std::env::args().skip(1))?)
+}
+
+/// Runs the day like its binary, for the ones taking more flags.
+pub fn day_run(day: u8, options: &Options) -> AdventResult<()> {
+    let reports = run_days(day..=day, options, &Answers::load()?)?;
+    check_answers(&reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_run() {
+        let command = Command::parse(args("run 7 --part 2 --sample -vv")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                day: 7,
+                options: Options {
+                    part: Some(Part::Two),
+                    use_sample: true,
+                    input: InputSource::Directory,
+                    format: Format::Text,
+                    verbosity: Verbosity::Trace,
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn parse_input_source() {
+        let options = Options::parse(args("--input inputs/day1.txt")).unwrap();
+        assert_eq!(options.input, InputSource::from_arg("inputs/day1.txt"));
+        let options = Options::parse(args("--input -")).unwrap();
+        assert_eq!(options.input, InputSource::Stdin);
+        let options = Options::parse(args("- --part 1")).unwrap();
+        assert_eq!(options.input, InputSource::Stdin);
+        assert!(Options::parse(args("--input")).is_err());
+    }
+
+    #[test]
+    fn parse_bench() {
+        let command =
+            Command::parse(args("bench 6 --iterations 5 --sample --baseline base.txt")).unwrap();
+        let Command::Bench {
+            day,
+            options,
+            bench,
+        } = command
+        else {
+            panic!("not a bench command");
+        };
+        assert_eq!(day, Some(6));
+        assert!(options.use_sample);
+        assert_eq!(bench.config.iterations, 5);
+        assert_eq!(bench.baseline, Some(PathBuf::from("base.txt")));
+        let command = Command::parse(args("bench --warmup 1")).unwrap();
+        assert!(matches!(command, Command::Bench { day: None, .. }));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(Command::parse(args("run")).is_err());
+        assert!(Command::parse(args("all --part 3")).is_err());
+        assert!(Command::parse(args("walk 1")).is_err());
+        assert!(Command::parse(args("all --format xml")).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::{input_lines, AdventResult, Detailed, InputLine, Solution};
+
+fn input_parse(input: &str) -> AdventResult<Vec<i32>> {
+    let mut result = vec![];
+    for line in input_lines(Day1::DAY, input) {
+        let buf = line.text.trim();
+        if buf.is_empty() {
+            continue;
+        }
+        result.push(line.parse::<i32>(buf, "a depth measurement")?);
+    }
+    Ok(result)
+}
+
+/// How the sum of a window compares to the one of the window before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Increasing,
+    Decreasing,
+    NonDecreasing,
+}
+
+impl Trend {
+    fn matches(self, previous: i64, current: i64) -> bool {
+        match self {
+            Trend::Increasing => current > previous,
+            Trend::Decreasing => current < previous,
+            Trend::NonDecreasing => current >= previous,
+        }
+    }
+}
+
+/// Readings allocated up front, larger windows grow as the readings come.
+const PREALLOCATED_READINGS: usize = 1024;
+
+/// Counts the windows following the trend as the depth readings come, one at a time.
+///
+/// Only the readings of the current window are kept, so the memory does not grow with the
+/// input. A window of 0 counts nothing.
+#[derive(Debug, Clone)]
+pub struct SweepAnalyser {
+    window: usize,
+    trend: Trend,
+    readings: VecDeque<i32>,
+    sum: i64,
+    counter: usize,
+    total: usize,
+}
+
+impl SweepAnalyser {
+    pub fn new(window: usize, trend: Trend) -> Self {
+        Self {
+            window,
+            trend,
+            readings: VecDeque::with_capacity(window.min(PREALLOCATED_READINGS)),
+            sum: 0,
+            counter: 0,
+            total: 0,
+        }
+    }
+
+    /// Adds a reading, returning the running count.
+    pub fn push(&mut self, depth: i32) -> usize {
+        self.total += 1;
+        if self.window == 0 {
+            return self.counter;
+        }
+        if self.readings.len() == self.window {
+            let leaving = self.readings.pop_front().unwrap_or_default();
+            let next = self.sum + depth as i64 - leaving as i64;
+            if self.trend.matches(self.sum, next) {
+                self.counter += 1;
+            }
+            self.sum = next;
+        } else {
+            self.sum += depth as i64;
+        }
+        self.readings.push_back(depth);
+        self.counter
+    }
+
+    /// Windows following the trend so far.
+    pub fn count(&self) -> usize {
+        self.counter
+    }
+
+    /// Readings seen so far.
+    pub fn readings(&self) -> usize {
+        self.total
+    }
+}
+
+impl Extend<i32> for SweepAnalyser {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, readings: I) {
+        for depth in readings {
+            self.push(depth);
+        }
+    }
+}
+
+/// Feeds the readings of `reader`, one per line, to the analyser. Blank lines are skipped.
+pub fn analyse_reader(
+    reader: impl BufRead,
+    window: usize,
+    trend: Trend,
+) -> AdventResult<SweepAnalyser> {
+    let mut analyser = SweepAnalyser::new(window, trend);
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = InputLine {
+            day: Day1::DAY,
+            number: index + 1,
+            text: &text,
+        };
+        let buf = line.text.trim();
+        if !buf.is_empty() {
+            analyser.push(line.parse::<i32>(buf, "a depth measurement")?);
+        }
+    }
+    Ok(analyser)
+}
+
+/// Counts the sliding windows of `window` measurements following the trend, in O(n)
+/// whatever the size. A window of 0, or larger than the measurements, counts nothing.
+pub fn count_trend(measurements: &[i32], window: usize, trend: Trend) -> usize {
+    let mut analyser = SweepAnalyser::new(window, trend);
+    analyser.extend(measurements.iter().copied());
+    analyser.count()
+}
+
+pub fn count_increases(measurements: &[i32], window: usize) -> usize {
+    count_trend(measurements, window, Trend::Increasing)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<i32>;
+    type Answer1 = Detailed<usize>;
+    type Answer2 = Detailed<usize>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        input_parse(input)
+    }
+
+    fn part_one(measurements: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let increases = count_increases(measurements, 1);
+        Ok(Detailed::new(increases).with("measurements", measurements.len()))
+    }
+
+    fn part_two(measurements: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let increases = count_increases(measurements, 3);
+        Ok(Detailed::new(increases).with("measurements", measurements.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdventError;
+
+    const SAMPLE: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn sample_windows() {
+        assert_eq!(count_increases(&SAMPLE, 1), 7);
+        assert_eq!(count_increases(&SAMPLE, 3), 5);
+        assert_eq!(count_trend(&SAMPLE, 1, Trend::Decreasing), 2);
+        assert_eq!(count_trend(&[1, 1, 2, 1], 1, Trend::NonDecreasing), 2);
+        assert_eq!(count_increases(&SAMPLE, 10), 0);
+        assert_eq!(count_increases(&SAMPLE, 0), 0);
+        assert_eq!(count_increases(&[], 1), 0);
+        assert_eq!(count_increases(&[199], 1), 0);
+    }
+
+    #[test]
+    fn streams_running_counts() {
+        let mut analyser = SweepAnalyser::new(3, Trend::Increasing);
+        let running: Vec<_> = SAMPLE.iter().map(|&depth| analyser.push(depth)).collect();
+        assert_eq!(running, vec![0, 0, 0, 1, 1, 1, 2, 3, 4, 5]);
+        assert_eq!(analyser.readings(), 10);
+        let mut analyser = SweepAnalyser::new(usize::MAX, Trend::Increasing);
+        assert_eq!(analyser.push(199), 0);
+
+        let analyser =
+            analyse_reader("199\n200\n\n208\n".as_bytes(), 1, Trend::Increasing).unwrap();
+        assert_eq!((analyser.count(), analyser.readings()), (2, 3));
+        let analyser = analyse_reader("".as_bytes(), 1, Trend::Increasing).unwrap();
+        assert_eq!((analyser.count(), analyser.readings()), (0, 0));
+        let error = analyse_reader("199\n2x0\n".as_bytes(), 1, Trend::Increasing).unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rolling_sum_matches_window_sums() {
+        let measurements: Vec<i32> = (0..200).map(|i| (i * 7919 % 101) - 50).collect();
+        for window in 1..=50 {
+            let sums: Vec<i32> = measurements
+                .windows(window)
+                .map(|w| w.iter().sum())
+                .collect();
+            let expected = sums.windows(2).filter(|pair| pair[1] > pair[0]).count();
+            assert_eq!(count_increases(&measurements, window), expected);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    info, input_lines, log_enabled, AdventError, AdventResult, Detailed, Solution, Verbosity,
+};
+
+const OPEN_BRACKETS: [char; 4] = ['(', '[', '{', '<'];
+const CLOSE_BRACKETS: [char; 4] = [')', ']', '}', '>'];
+const ERROR_POINTS: [u64; 4] = [3, 57, 1197, 25137];
+const FIX_POINTS: [u64; 4] = [1, 2, 3, 4];
+
+/// Checks the lines only have brackets.
+fn parse_input(input: &str) -> AdventResult<&str> {
+    for line in input_lines(Day10::DAY, input) {
+        if let Some((index, c)) = line
+            .text
+            .trim_end()
+            .char_indices()
+            .find(|(_, c)| !OPEN_BRACKETS.contains(c) && !CLOSE_BRACKETS.contains(c))
+        {
+            return Err(line.error(&line.text[index..index + c.len_utf8()], "a bracket"));
+        }
+    }
+    Ok(input)
+}
+
+/// The scores of the corrupted lines and of the completions of the incomplete ones.
+#[derive(Debug, Default)]
+struct Scores {
+    error_points: u64,
+    corrupted: usize,
+    /// Sorted, without the ones past `u64`.
+    fix_points: Vec<u64>,
+    /// Incomplete lines whose completion score overflows.
+    overflows: usize,
+}
+
+fn score_lines(input: &str) -> Scores {
+    let mut stack = vec![];
+    let mut error_points = 0;
+    let mut corrupted = 0;
+    let mut fix_points = vec![];
+    let mut overflows = 0;
+
+    for (line_no, line) in input.lines().enumerate() {
+        stack.clear();
+        let mut error = false;
+        for (index, bracket) in line.chars().enumerate() {
+            if OPEN_BRACKETS.contains(&bracket) {
+                stack.push(bracket);
+            } else if CLOSE_BRACKETS.contains(&bracket) {
+                let pos = CLOSE_BRACKETS.iter().position(|&b| b == bracket).unwrap();
+                if !stack.is_empty()
+                    && stack
+                        .last()
+                        .map_or_else(|| false, |&b| b == OPEN_BRACKETS[pos])
+                {
+                    stack.pop();
+                } else if let Some(last_opened) = stack.last() {
+                    info!(
+                        "Mismatch in line {:03}:{:03}: expected {} found {}",
+                        line_no + 1,
+                        index + 1,
+                        CLOSE_BRACKETS
+                            [OPEN_BRACKETS.iter().position(|b| b == last_opened).unwrap()],
+                        bracket
+                    );
+                    error = true;
+                    error_points += ERROR_POINTS[pos];
+                    corrupted += 1;
+                    break;
+                }
+            }
+        }
+
+        if !stack.is_empty() && !error {
+            if log_enabled(Verbosity::Info) {
+                let missing: String = stack
+                    .iter()
+                    .rev()
+                    .map(|o| OPEN_BRACKETS.iter().position(|c| c == o).unwrap())
+                    .map(|p| CLOSE_BRACKETS[p])
+                    .collect();
+                eprintln!("{}|{}", line, missing);
+            }
+            let points = stack
+                .iter()
+                .rev()
+                .map(|o| OPEN_BRACKETS.iter().position(|c| c == o).unwrap())
+                .map(|p| FIX_POINTS[p])
+                .try_fold(0_u64, |total, v| total.checked_mul(5)?.checked_add(v));
+            match points {
+                Some(points) => fix_points.push(points),
+                None => overflows += 1,
+            }
+        }
+    }
+    fix_points.sort_unstable();
+    Scores {
+        error_points,
+        corrupted,
+        fix_points,
+        overflows,
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = &'a str;
+    type Answer1 = Detailed<u64>;
+    type Answer2 = Detailed<u64>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let scores = score_lines(input);
+        Ok(Detailed::new(scores.error_points).with("corrupted_lines", scores.corrupted))
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let scores = score_lines(input);
+        if scores.overflows > 0 {
+            return Err(AdventError::Overflow(format!(
+                "the completion score of {} lines",
+                scores.overflows
+            )));
+        }
+        let fix_points = &scores.fix_points;
+        let middle = fix_points
+            .get(fix_points.len() / 2)
+            .ok_or(AdventError::InvalidData)?;
+        Ok(Detailed::new(*middle).with("incomplete_lines", fix_points.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_characters() {
+        let error = parse_input("()\n(é)\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 2,
+                column: 2,
+                ref found,
+                ..
+            } if found == "é"
+        ));
+    }
+
+    #[test]
+    fn line_counts() {
+        let scores = score_lines("{([(<{}[<>[]}>{[]{[(<()>\n[({(<(())[]>[[{[]{<()<>>\n([])\n");
+        assert_eq!((scores.error_points, scores.corrupted), (1197, 1));
+        assert_eq!(scores.fix_points, vec![288957]);
+        let corrupted_only = parse_input("(]\n").unwrap();
+        assert!(matches!(
+            Day10::part_two(&corrupted_only),
+            Err(AdventError::InvalidData)
+        ));
+        assert_eq!(Day10::part_one(&"").unwrap().answer, 0);
+    }
+
+    #[test]
+    fn long_completion() {
+        let input = "<".repeat(27) + "\n" + &"<".repeat(28) + "\n";
+        let scores = score_lines(&input);
+        assert_eq!(scores.fix_points, vec![5_u64.pow(27) - 1]);
+        assert_eq!(scores.overflows, 1);
+        assert!(matches!(
+            Day10::part_two(&input.as_str()),
+            Err(AdventError::Overflow(_))
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{grid::Grid2D, info, log_enabled, AdventResult, Detailed, Solution, Verbosity};
+
+/// Energy levels of the octopus.
+#[derive(Debug, Default, Clone)]
+pub struct Octopuses {
+    energy: Grid2D<u8>,
+}
+
+impl Octopuses {
+    fn part_1(&mut self, steps: usize) -> usize {
+        let mut counter = 0;
+        for step in 0..steps {
+            info!("Step {}", step + 1);
+            counter += self.step().len();
+        }
+        counter
+    }
+
+    /// Loop until all the octopus flash
+    fn part_2(&mut self) -> usize {
+        let mut step = 0;
+        loop {
+            step += 1;
+            info!("Step {}", step);
+            if self.step().len() == self.energy.len() {
+                break;
+            }
+        }
+        step
+    }
+
+    /// Raises every energy level, returning the octopus that flashed.
+    fn step(&mut self) -> Vec<(usize, usize)> {
+        let mut flashed = vec![];
+        for (x, y) in self.energy.positions() {
+            self.iterate_octopus(x, y, &mut flashed);
+        }
+        if log_enabled(Verbosity::Trace) {
+            self.display(&flashed);
+        }
+        flashed
+    }
+
+    /// Prints the energy levels, the octopus that did not flash are greyed out.
+    fn display(&self, flashed: &[(usize, usize)]) {
+        for (position, energy) in self.energy.iter() {
+            if flashed.contains(&position) {
+                eprint!("{}", energy);
+            } else {
+                eprint!("\x1B[38;5;8m{}\x1B[0m", energy);
+            }
+            if position.0 + 1 == self.energy.width() {
+                eprintln!();
+            }
+        }
+        eprintln!();
+    }
+
+    fn iterate_octopus(&mut self, x: usize, y: usize, flashed: &mut Vec<(usize, usize)>) {
+        if flashed.contains(&(x, y)) {
+            return;
+        }
+        let energy = &mut self.energy[(x, y)];
+        *energy += 1;
+        if *energy > 9 {
+            *energy = 0;
+            flashed.push((x, y));
+            for (nx, ny) in self.energy.neighbours8(x, y) {
+                self.iterate_octopus(nx, ny, flashed);
+            }
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Octopuses;
+    type Answer1 = Detailed<usize>;
+    type Answer2 = Detailed<usize>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        Ok(Octopuses {
+            energy: Grid2D::from_digits(Self::DAY, input)?,
+        })
+    }
+
+    fn part_one(grid: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        Ok(Detailed::new(grid.clone().part_1(100)).with("octopuses", grid.energy.len()))
+    }
+
+    fn part_two(grid: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        Ok(Detailed::new(grid.clone().part_2()).with("octopuses", grid.energy.len()))
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    graph::{Graph, NodeId, OneTwice, VisitOnce, VisitPolicy},
+    input_lines, log_enabled, AdventError, AdventResult, Detailed, Solution, Verbosity,
+};
+
+/// The caves and their connections, small caves have lowercase names.
+#[derive(Debug)]
+pub struct Caves<'a> {
+    graph: Graph<&'a str>,
+    small: Vec<bool>,
+    start: NodeId,
+    end: NodeId,
+}
+
+impl<'a> Caves<'a> {
+    fn is_small(&self, id: NodeId) -> bool {
+        self.small[id]
+    }
+
+    fn count_paths(&self, policy: &impl VisitPolicy) -> u32 {
+        let mut count = 0;
+        self.graph.paths(self.start, self.end, policy, |path| {
+            if log_enabled(Verbosity::Trace) {
+                let names: Vec<_> = path.iter().map(|&id| *self.graph.name(id)).collect();
+                eprintln!("{}", names.join("-"));
+            }
+            count += 1;
+        });
+        count
+    }
+
+    fn detailed(&self, paths: u32) -> Detailed<u32> {
+        Detailed::new(paths)
+            .with("caves", self.small.len())
+            .with("small_caves", self.small.iter().filter(|&&s| s).count())
+    }
+
+    /// Small caves are visited at most once.
+    fn part_1(&self) -> u32 {
+        self.count_paths(&VisitOnce(|id| self.is_small(id)))
+    }
+
+    /// A single small cave, other than start, may be visited twice.
+    fn part_2(&self) -> u32 {
+        self.count_paths(&OneTwice(|id| self.is_small(id)))
+    }
+}
+
+fn parse_input(input: &str) -> AdventResult<Caves<'_>> {
+    let mut graph = Graph::undirected();
+    for line in input_lines(Day12::DAY, input) {
+        let clean_buffer = line.text.trim();
+        if clean_buffer.is_empty() {
+            continue;
+        }
+        let (a, b) = clean_buffer
+            .split_once('-')
+            .ok_or_else(|| line.error_at_end("'-'"))?;
+        for cave in [a, b] {
+            if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(line.error(cave, "a cave name"));
+            }
+        }
+        graph.add_edge(a, b);
+    }
+    let (Some(start), Some(end)) = (graph.id(&"start"), graph.id(&"end")) else {
+        return Err(AdventError::InvalidData);
+    };
+    let small = graph
+        .nodes()
+        .map(|id| graph.name(id).chars().all(|c| c.is_ascii_lowercase()))
+        .collect();
+    Ok(Caves {
+        graph,
+        small,
+        start,
+        end,
+    })
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input<'a> = Caves<'a>;
+    type Answer1 = Detailed<u32>;
+    type Answer2 = Detailed<u32>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(caves: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        Ok(caves.detailed(caves.part_1()))
+    }
+
+    fn part_two(caves: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        Ok(caves.detailed(caves.part_2()))
+    }
+}
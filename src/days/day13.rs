@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+
+use crate::{
+    grid::Grid2D, input_lines, log_enabled, AdventError, AdventResult, Detailed, Solution,
+    Verbosity,
+};
+
+#[derive(Debug)]
+pub enum Fold {
+    X(u32),
+    Y(u32),
+}
+
+/// A `width` by `height` sheet. The folds move the positions of its dots so the coordinates
+/// can be large, it is drawn on a [`Grid2D`] once folded to a readable size.
+#[derive(Debug, Default, Clone)]
+pub struct Dots {
+    positions: HashSet<(usize, usize)>,
+    width: usize,
+    height: usize,
+}
+
+impl Dots {
+    fn is_dot(&self, x: usize, y: usize) -> bool {
+        self.positions.contains(&(x, y))
+    }
+
+    fn sheet(&self) -> Grid2D<bool> {
+        Grid2D::from_fn(self.width, self.height, |x, y| self.is_dot(x, y))
+    }
+}
+
+/// Where a coordinate ends up once folded along `pos`, `None` on the line or too far to land
+/// on the paper, which the parser rejects.
+fn fold_coordinate(c: usize, pos: usize) -> Option<usize> {
+    match c {
+        c if c < pos => Some(c),
+        c if c > pos && c <= 2 * pos => Some(2 * pos - c),
+        _ => None,
+    }
+}
+
+impl Fold {
+    /// Folds the paper, the dots end up on the half above or left of the line. Fails with a
+    /// dot on the line or past its mirror.
+    fn fold(&self, dots: &Dots) -> Result<Dots, (usize, usize)> {
+        let folded = |x, y| match *self {
+            Fold::X(pos) => fold_coordinate(x, pos as usize).map(|x| (x, y)),
+            Fold::Y(pos) => fold_coordinate(y, pos as usize).map(|y| (x, y)),
+        };
+        let positions = dots
+            .positions
+            .iter()
+            .map(|&(x, y)| folded(x, y).ok_or((x, y)))
+            .collect::<Result<_, _>>()?;
+        let (width, height) = match *self {
+            Fold::X(pos) => (pos as usize, dots.height),
+            Fold::Y(pos) => (dots.width, pos as usize),
+        };
+        Ok(Dots {
+            positions,
+            width,
+            height,
+        })
+    }
+
+    fn position(&self) -> usize {
+        match *self {
+            Fold::X(pos) | Fold::Y(pos) => pos as usize,
+        }
+    }
+}
+
+fn count(dots: &Dots) -> usize {
+    dots.positions.len()
+}
+
+/// Dots and the folds to apply.
+type Paper = (Dots, Vec<Fold>);
+
+fn parse_input(input: &str) -> AdventResult<Paper> {
+    let mut dots = vec![];
+    let mut folds = vec![];
+    for line in input_lines(Day13::DAY, input) {
+        let clean_buffer = line.text.trim();
+        if clean_buffer.is_empty() {
+            continue;
+        }
+        if let Some(fold) = clean_buffer.strip_prefix("fold along ") {
+            let (axis, position) = fold
+                .split_once('=')
+                .ok_or_else(|| line.error(fold, "x=N or y=N"))?;
+            let value = line.parse::<u32>(position, "a fold position")?;
+            let fold = match axis {
+                "x" => Fold::X(value),
+                "y" => Fold::Y(value),
+                _ => return Err(line.error(axis, "x or y")),
+            };
+            folds.push((fold, line, position));
+        } else {
+            let (x, y) = clean_buffer
+                .split_once(',')
+                .ok_or_else(|| line.error(clean_buffer, "x,y or a fold"))?;
+            dots.push((
+                line.parse::<usize>(x, "a coordinate")?,
+                line.parse::<usize>(y, "a coordinate")?,
+            ));
+        }
+    }
+    let paper = Dots {
+        width: dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+        height: dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0),
+        positions: dots.into_iter().collect(),
+    };
+    // Every fold must move all the dots onto the paper
+    let mut folded = paper.clone();
+    for (fold, line, position) in &folds {
+        folded = fold.fold(&folded).map_err(|(x, y)| {
+            let coordinate = match fold {
+                Fold::X(_) => x,
+                Fold::Y(_) => y,
+            };
+            let problem = if coordinate == fold.position() {
+                "is on it"
+            } else {
+                "folds off the paper"
+            };
+            line.error(
+                position,
+                format!("a fold line where the dot at {},{} {}", x, y, problem),
+            )
+        })?;
+    }
+    Ok((paper, folds.into_iter().map(|(fold, ..)| fold).collect()))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Paper;
+    type Answer1 = Detailed<usize>;
+    type Answer2 = Detailed<String>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one((dots, folds): &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let first = folds.first().ok_or(AdventError::InvalidData)?;
+        if log_enabled(Verbosity::Trace) {
+            display(dots, Some(first));
+        }
+        let dots = first.fold(dots).map_err(|_| AdventError::InvalidData)?;
+        if log_enabled(Verbosity::Trace) {
+            display(&dots, None);
+        }
+        Ok(Detailed::new(count(&dots))
+            .with("width", dots.width)
+            .with("height", dots.height))
+    }
+
+    /// The answer is the code drawn by the dots after all the folds.
+    fn part_two((dots, folds): &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let dots = folds
+            .iter()
+            .try_fold(dots.clone(), |dots, fold| fold.fold(&dots))
+            .map_err(|_| AdventError::InvalidData)?;
+        Ok(Detailed::new(render(&dots))
+            .with("dots", count(&dots))
+            .with("folds", folds.len()))
+    }
+}
+
+/// Draws the dots, one line per row, starting with a new line so it reads well after a label.
+fn render(dots: &Dots) -> String {
+    let mut result = String::new();
+    for row in dots.sheet().rows() {
+        result.push('\n');
+        result.extend(row.iter().map(|&dot| if dot { '\u{2588}' } else { ' ' }));
+    }
+    result
+}
+
+/// Prints the dots to stderr, with the fold line in red.
+fn display(dots: &Dots, fold: Option<&Fold>) {
+    let sheet = dots.sheet();
+    for ((x, y), &dot) in sheet.iter() {
+        let on_fold = match fold {
+            Some(&Fold::X(pos)) => pos as usize == x,
+            Some(&Fold::Y(pos)) => pos as usize == y,
+            None => false,
+        };
+        if on_fold {
+            eprint!("\x1B[38;5;1m\u{2588}\x1B[0m");
+        } else if dot {
+            eprint!("\u{2588}");
+        } else {
+            eprint!("\x1B[38;5;8m\u{2588}\x1B[0m");
+        }
+        if x + 1 == sheet.width() {
+            eprintln!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_coordinates() {
+        let (dots, folds) =
+            parse_input("3000000,3000000\n0,0\n2999999,1\n\nfold along x=1500000\n").unwrap();
+        let folded = folds[0].fold(&dots).unwrap();
+        assert_eq!((folded.width, folded.height), (1500000, 3000001));
+        assert_eq!(count(&folded), 3);
+        assert!(folded.is_dot(0, 3000000) && folded.is_dot(1, 1));
+    }
+
+    #[test]
+    fn dots_off_the_folds() {
+        let error = parse_input("1,2\n3,4\n\nfold along y=5\nfold along x=3\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse { line: 5, column: 14, ref expected, .. }
+                if expected.ends_with("3,4 is on it")
+        ));
+        let error = parse_input("1,2\n1,9\n\nfold along y=4\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse { line: 4, ref expected, .. }
+                if expected.ends_with("1,9 folds off the paper")
+        ));
+    }
+}
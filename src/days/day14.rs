@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use crate::{
+    input_lines, log_enabled, AdventError, AdventResult, Detailed, Part, Solution, Variant,
+    Verbosity,
+};
+
+#[derive(Debug, Default)]
+pub struct Synthesis<'a> {
+    template: &'a str,
+    rules: HashMap<(u8, u8), u8>,
+}
+
+impl<'a> Synthesis<'a> {
+    fn grow_brute_force(&self, cycles: usize) -> AdventResult<(usize, Vec<u8>)> {
+        let mut counter = HashMap::<u8, usize>::new();
+        let mut chain = self.template.as_bytes().to_vec();
+
+        for &b in chain.iter() {
+            *counter.entry(b).or_insert(0) += 1;
+        }
+
+        for _ in 0..cycles {
+            let pairs: Vec<_> = chain
+                .iter()
+                .zip(chain.iter().skip(1))
+                .map(|(&a, &b)| [a, b])
+                .collect();
+            let mut position = 1;
+            for pair in pairs.iter() {
+                for (rule_pair, &element) in self.rules.iter() {
+                    if pair[0] == rule_pair.0 && pair[1] == rule_pair.1 {
+                        chain.insert(position, element);
+                        *counter.entry(element).or_insert(0) += 1;
+                        position += 2;
+                    }
+                }
+            }
+            if log_enabled(Verbosity::Trace) {
+                display(&chain);
+            }
+        }
+        let min = counter.values().min().ok_or(AdventError::InvalidData)?;
+        let max = counter.values().max().ok_or(AdventError::InvalidData)?;
+
+        Ok(((max - min), chain))
+    }
+
+    /// Smarter grow  
+    /// The main drawback is that we can't get the produced chain order
+    /// we only get the number of pairs, but it is very efficient.
+    fn grow(&self, cycles: usize) -> AdventResult<usize> {
+        let mut counter = HashMap::<u8, usize>::new();
+        let mut chain = HashMap::<(u8, u8), usize>::new();
+        let mut pairs = Vec::new();
+
+        // Count our input template
+        for b in self.template.bytes() {
+            *counter.entry(b).or_insert(0) += 1;
+        }
+
+        // Prepare the first pairs
+        for pair in self.template.bytes().zip(self.template.bytes().skip(1)) {
+            *chain.entry(pair).or_insert(0) += 1;
+        }
+
+        // Drain keeps the current capacity, so we have a constant alloc.
+        for _ in 0..cycles {
+            pairs.extend(chain.drain().filter(|(_, count)| *count > 0));
+            for (pair, pair_count) in pairs.iter() {
+                if *pair_count > 0 {
+                    if let Some(&element) = self.rules.get(pair) {
+                        *counter.entry(element).or_insert(0) += pair_count;
+                        *chain.entry((pair.0, element)).or_insert(0) += pair_count;
+                        *chain.entry((element, pair.1)).or_insert(0) += pair_count;
+                    }
+                }
+            }
+            if log_enabled(Verbosity::Trace) {
+                display_pairs(&chain);
+            }
+            // Clear also keeps the current capacity so we avoid extra allocations from the extend.
+            pairs.clear();
+        }
+        let min = counter.values().min().ok_or(AdventError::InvalidData)?;
+        let max = counter.values().max().ok_or(AdventError::InvalidData)?;
+
+        Ok(max - min)
+    }
+}
+
+fn display(chain: &[u8]) {
+    eprintln!("{}", String::from_utf8_lossy(chain))
+}
+
+fn display_pairs(chain: &HashMap<(u8, u8), usize>) {
+    for (pair, size) in chain {
+        eprintln!("{} {}", String::from_utf8_lossy(&[pair.0, pair.1]), size);
+    }
+}
+
+/// The template comes first, then the insertion rules in the form `AB -> C`.
+fn parse_input(input: &str) -> AdventResult<Synthesis<'_>> {
+    let mut synthesis = Synthesis::default();
+    for line in input_lines(Day14::DAY, input) {
+        let clean_buffer = line.text.trim();
+        if clean_buffer.is_empty() {
+            continue;
+        }
+        if synthesis.template.is_empty() {
+            if let Some((index, c)) = clean_buffer
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_uppercase())
+            {
+                let token = &clean_buffer[index..index + c.len_utf8()];
+                return Err(line.error(token, "an element"));
+            }
+            synthesis.template = clean_buffer;
+            continue;
+        }
+        let (a, b) = clean_buffer
+            .split_once("->")
+            .ok_or_else(|| line.error_at_end("'->'"))?;
+        let (pair, element) = (a.trim(), b.trim());
+        if pair.len() != 2 || !pair.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(line.error(pair, "a pair of elements"));
+        }
+        if element.len() != 1 || !element.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(line.error(element, "an element"));
+        }
+        let pair = pair.as_bytes();
+        synthesis
+            .rules
+            .entry((pair[0], pair[1]))
+            .or_insert_with(|| element.as_bytes()[0]);
+    }
+    Ok(synthesis)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input<'a> = Synthesis<'a>;
+    type Answer1 = Detailed<usize>;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(synthesis: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let (difference, polymer) = synthesis.grow_brute_force(10)?;
+        Ok(Detailed::new(difference).with("polymer_length", polymer.len()))
+    }
+
+    fn part_two(synthesis: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        synthesis.grow(40)
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![Variant {
+            name: "grow",
+            part: Part::One,
+            solve: |synthesis| Ok(synthesis.grow(10)?.to_string()),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_template() {
+        let error = parse_input("NNé\n\nNN -> C\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 1,
+                column: 3,
+                ref found,
+                ..
+            } if found == "é"
+        ));
+    }
+
+    #[test]
+    fn empty_template() {
+        let synthesis = parse_input("").unwrap();
+        assert!(matches!(
+            synthesis.grow_brute_force(10),
+            Err(AdventError::InvalidData)
+        ));
+        assert!(matches!(synthesis.grow(40), Err(AdventError::InvalidData)));
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{input_lines, AdventError, AdventResult, Detailed, Solution};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Forward(i64),
+    /// The opposite of forward.
+    Back(i64),
+    Down(i64),
+    Up(i64),
+    /// Straight back to the surface, the aim levelled.
+    Surface,
+    /// Stays in place for a step.
+    Hold,
+    /// Runs the block the given number of times.
+    Repeat(u32, Vec<Command>),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: i64,
+    pub depth: i64,
+    /// Only used by [`MovementModel::Aim`].
+    pub aim: i64,
+}
+
+impl Position {
+    /// `None` when it overflows.
+    pub fn factor(&self) -> Option<i64> {
+        self.horizontal.checked_mul(self.depth)
+    }
+
+    fn detailed(&self) -> AdventResult<Detailed<i64>> {
+        let factor = self.factor().ok_or_else(|| {
+            AdventError::Overflow(format!("{} times {}", self.horizontal, self.depth))
+        })?;
+        Ok(Detailed::new(factor)
+            .with("horizontal", self.horizontal)
+            .with("depth", self.depth))
+    }
+}
+
+/// Reads the commands, one per line:
+///
+/// - `forward N`, `back N`, `down N`, `up N`, `surface` or `hold`,
+/// - `repeat N {` up to a matching `}` line, the block between them runs N times,
+/// - `#` starts a comment up to the end of the line.
+fn parse_input(input: &str) -> AdventResult<Vec<Command>> {
+    let mut commands = vec![];
+    // The repeat blocks being read, with the commands around them.
+    let mut blocks = vec![];
+
+    for line in input_lines(Day2::DAY, input) {
+        let code = line.text.split('#').next().unwrap_or_default().trim();
+        if code.is_empty() {
+            continue;
+        }
+        let mut tokens = code.split_whitespace();
+        let verb = tokens.next().unwrap_or_default();
+        let mut amount = || -> AdventResult<i64> {
+            let token = tokens
+                .next()
+                .ok_or_else(|| line.error_at_end("an amount"))?;
+            line.parse(token, "an amount")
+        };
+        let command = match verb {
+            "forward" => Command::Forward(amount()?),
+            "back" => Command::Back(amount()?),
+            "down" => Command::Down(amount()?),
+            "up" => Command::Up(amount()?),
+            "surface" => Command::Surface,
+            "hold" => Command::Hold,
+            "repeat" => {
+                let times = tokens
+                    .next()
+                    .ok_or_else(|| line.error_at_end("a repeat count"))?;
+                let times = line.parse::<u32>(times, "a repeat count")?;
+                match tokens.next() {
+                    Some("{") => {}
+                    Some(token) => return Err(line.error(token, "'{'")),
+                    None => return Err(line.error_at_end("'{'")),
+                }
+                blocks.push((line, times, std::mem::take(&mut commands)));
+                continue;
+            }
+            "}" => {
+                let (_, times, outer) =
+                    blocks.pop().ok_or_else(|| line.error(verb, "a command"))?;
+                let block = std::mem::replace(&mut commands, outer);
+                Command::Repeat(times, block)
+            }
+            _ => {
+                return Err(line.error(
+                    verb,
+                    "forward, back, down, up, surface, hold, repeat or '}'",
+                ))
+            }
+        };
+        if let Some(token) = tokens.next() {
+            return Err(line.error(token, "the end of the line"));
+        }
+        commands.push(command);
+    }
+    if let Some((start, _, _)) = blocks.pop() {
+        return Err(start.error_at_end("a closing '}' line"));
+    }
+    Ok(commands)
+}
+
+/// How `down` and `up` move the submarine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementModel {
+    /// They change the depth directly, as in part 1.
+    Simple,
+    /// They change the aim, which `forward` then follows, as in part 2.
+    Aim,
+}
+
+/// Runs the commands, optionally keeping every position along the way.
+#[derive(Debug, Clone)]
+pub struct Submarine {
+    model: MovementModel,
+    position: Position,
+    trajectory: Option<Vec<Position>>,
+}
+
+impl Submarine {
+    pub fn new(model: MovementModel) -> Self {
+        Self {
+            model,
+            position: Position::default(),
+            trajectory: None,
+        }
+    }
+
+    /// Records the trajectory, starting with the surface position.
+    pub fn with_trajectory(mut self) -> Self {
+        self.trajectory = Some(vec![self.position]);
+        self
+    }
+
+    /// Fails when the position overflows, leaving it and the trajectory as they were before
+    /// the command, a whole repeat block included.
+    pub fn execute(&mut self, command: &Command) -> AdventResult<()> {
+        let mut pos = self.position;
+        let overflow = || AdventError::Overflow(format!("the position after {:?}", command));
+        let add = |a: i64, b: i64| a.checked_add(b).ok_or_else(overflow);
+        let sub = |a: i64, b: i64| a.checked_sub(b).ok_or_else(overflow);
+        let mul = |a: i64, b: i64| a.checked_mul(b).ok_or_else(overflow);
+        match (self.model, command) {
+            (_, Command::Repeat(times, block)) => {
+                let recorded = self.trajectory.as_ref().map_or(0, Vec::len);
+                for _ in 0..*times {
+                    if let Err(error) = self.run(block) {
+                        self.position = pos;
+                        if let Some(trajectory) = &mut self.trajectory {
+                            trajectory.truncate(recorded);
+                        }
+                        return Err(error);
+                    }
+                }
+                return Ok(());
+            }
+            (_, Command::Surface) => {
+                pos.depth = 0;
+                pos.aim = 0;
+            }
+            (_, Command::Hold) => {}
+            (MovementModel::Simple, Command::Up(amount)) => pos.depth = sub(pos.depth, *amount)?,
+            (MovementModel::Simple, Command::Down(amount)) => pos.depth = add(pos.depth, *amount)?,
+            (MovementModel::Simple, Command::Forward(amount)) => {
+                pos.horizontal = add(pos.horizontal, *amount)?
+            }
+            (MovementModel::Simple, Command::Back(amount)) => {
+                pos.horizontal = sub(pos.horizontal, *amount)?
+            }
+            (MovementModel::Aim, Command::Up(amount)) => pos.aim = sub(pos.aim, *amount)?,
+            (MovementModel::Aim, Command::Down(amount)) => pos.aim = add(pos.aim, *amount)?,
+            (MovementModel::Aim, Command::Forward(amount)) => {
+                pos.depth = add(pos.depth, mul(pos.aim, *amount)?)?;
+                pos.horizontal = add(pos.horizontal, *amount)?;
+            }
+            (MovementModel::Aim, Command::Back(amount)) => {
+                pos.depth = sub(pos.depth, mul(pos.aim, *amount)?)?;
+                pos.horizontal = sub(pos.horizontal, *amount)?;
+            }
+        }
+        self.position = pos;
+        if let Some(trajectory) = &mut self.trajectory {
+            trajectory.push(self.position);
+        }
+        Ok(())
+    }
+
+    /// Executes every command, returning the final position. Stops at the first one that
+    /// fails, the previous ones staying applied.
+    pub fn run(&mut self, commands: &[Command]) -> AdventResult<Position> {
+        for command in commands {
+            self.execute(command)?;
+        }
+        Ok(self.position)
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// The positions after each command, `None` unless recorded.
+    pub fn trajectory(&self) -> Option<&[Position]> {
+        self.trajectory.as_deref()
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Command>;
+    type Answer1 = Detailed<i64>;
+    type Answer2 = Detailed<i64>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(commands: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        Submarine::new(MovementModel::Simple)
+            .run(commands)?
+            .detailed()
+    }
+
+    fn part_two(commands: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        Submarine::new(MovementModel::Aim).run(commands)?.detailed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{read_input, AdventError};
+
+    #[test]
+    fn validate() {
+        let input = read_input(2, true).expect("Invalid data");
+        let commands = parse_input(&input).expect("Invalid data");
+        let pos = Submarine::new(MovementModel::Simple)
+            .run(&commands)
+            .unwrap();
+        assert_eq!((pos.horizontal, pos.depth), (15, 10));
+        assert_eq!(pos.factor(), Some(150));
+        let pos = Submarine::new(MovementModel::Aim).run(&commands).unwrap();
+        assert_eq!(pos.horizontal, 15);
+        assert_eq!(pos.depth, 60);
+        assert_eq!(pos.factor(), Some(900));
+    }
+
+    #[test]
+    fn trajectory() {
+        let commands = parse_input("forward 5\ndown 5\nforward 8").unwrap();
+        let mut submarine = Submarine::new(MovementModel::Aim).with_trajectory();
+        submarine.run(&commands).unwrap();
+        let depths: Vec<_> = submarine
+            .trajectory()
+            .unwrap()
+            .iter()
+            .map(|p| p.depth)
+            .collect();
+        assert_eq!(depths, vec![0, 0, 0, 40]);
+        assert_eq!(Submarine::new(MovementModel::Simple).trajectory(), None);
+    }
+
+    #[test]
+    fn unknown_command() {
+        let error = parse_input("forward 5\nbackward 2").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn command_language() {
+        let script = "# Dive profile\nrepeat 2 {\n  forward 2 # cruise\n  repeat 3 {\n    down 1\n  }\n}\nhold\nback 1\nsurface\n";
+        let commands = parse_input(script).unwrap();
+        let dive = vec![
+            Command::Forward(2),
+            Command::Repeat(3, vec![Command::Down(1)]),
+        ];
+        assert_eq!(
+            commands,
+            vec![
+                Command::Repeat(2, dive),
+                Command::Hold,
+                Command::Back(1),
+                Command::Surface,
+            ]
+        );
+        let mut submarine = Submarine::new(MovementModel::Simple).with_trajectory();
+        submarine.run(&commands[..1]).unwrap();
+        assert_eq!(submarine.position().depth, 6);
+        assert_eq!(submarine.trajectory().unwrap().len(), 9);
+        let pos = submarine.run(&commands[1..]).unwrap();
+        assert_eq!((pos.horizontal, pos.depth), (3, 0));
+        let pos = Submarine::new(MovementModel::Aim)
+            .run(&commands[..3])
+            .unwrap();
+        // The second forward dives 6 with an aim of 3, back undoes it with an aim of 6
+        assert_eq!((pos.horizontal, pos.depth), (3, 0));
+    }
+
+    #[test]
+    fn unbalanced_blocks() {
+        let error = parse_input("forward 1\nrepeat 2 {\nup 1\n").unwrap_err();
+        assert!(matches!(error, AdventError::Parse { line: 2, .. }));
+        let error = parse_input("forward 1\n}\n").unwrap_err();
+        assert!(matches!(error, AdventError::Parse { line: 2, .. }));
+        let error = parse_input("repeat 2\n}\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 1,
+                column: 9,
+                ..
+            }
+        ));
+        let error = parse_input("hold 3\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 1,
+                column: 6,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn overflow() {
+        let commands = parse_input("down 1\nrepeat 100000 {\n  forward 100000\n}\n").unwrap();
+        let pos = Submarine::new(MovementModel::Aim).run(&commands).unwrap();
+        assert_eq!(
+            (pos.horizontal, pos.depth),
+            (10_000_000_000, 10_000_000_000)
+        );
+        assert!(matches!(
+            Day2::part_two(&commands),
+            Err(AdventError::Overflow(_))
+        ));
+        let script = "down 9223372036854775807\nrepeat 2 {\n  forward 1\n}\n";
+        let commands = parse_input(script).unwrap();
+        let mut submarine = Submarine::new(MovementModel::Aim).with_trajectory();
+        assert!(matches!(
+            submarine.run(&commands),
+            Err(AdventError::Overflow(_))
+        ));
+        // The first iteration of the block is undone with the rest
+        let pos = submarine.position();
+        assert_eq!((pos.horizontal, pos.depth, pos.aim), (0, 0, i64::MAX));
+        assert_eq!(submarine.trajectory().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+
+use crate::{input_lines, AdventError, AdventResult, Detailed, Solution};
+
+/// The diagnostic codes, each packed into an integer, the first digit being the most
+/// significant bit.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DiagnosticReport {
+    codes: Vec<u64>,
+    /// Number of binary digits of every code, up to 64.
+    width: u32,
+}
+
+impl DiagnosticReport {
+    /// Reads the diagnostic codes, they must all be binary and of the same width.
+    pub fn parse(input: &str) -> AdventResult<Self> {
+        let mut report = Self::default();
+        for line in input_lines(Day3::DAY, input) {
+            let clean_buffer = line.text.trim();
+            if clean_buffer.is_empty() {
+                continue;
+            }
+            if let Some((index, c)) = clean_buffer
+                .char_indices()
+                .find(|&(_, c)| c != '0' && c != '1')
+            {
+                let token = &clean_buffer[index..index + c.len_utf8()];
+                return Err(line.error(token, "a binary digit"));
+            }
+            if report.codes.is_empty() {
+                if clean_buffer.len() > 64 {
+                    return Err(line.error(clean_buffer, "at most 64 binary digits"));
+                }
+                report.width = clean_buffer.len() as u32;
+            } else if clean_buffer.len() != report.width as usize {
+                return Err(line.error(clean_buffer, format!("{} binary digits", report.width)));
+            }
+            report.codes.push(u64::from_str_radix(clean_buffer, 2)?);
+        }
+        Ok(report)
+    }
+
+    pub fn codes(&self) -> &[u64] {
+        &self.codes
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The most common bits, a tie counting as a 1, and the least common bits, a tie also
+    /// counting as a 1.
+    pub fn gamma_epsilon(&self) -> (u64, u64) {
+        let mut gamma = 0;
+        let mut epsilon = 0;
+        for bit in 0..self.width {
+            let ones = count_ones(&self.codes, bit);
+            let zeros = self.codes.len() - ones;
+            if ones >= zeros {
+                gamma |= 1 << bit;
+            }
+            if zeros >= ones {
+                epsilon |= 1 << bit;
+            }
+        }
+        (gamma, epsilon)
+    }
+
+    /// Keeps the codes with the most common bit, or 1 on ties, from the most significant
+    /// bit down until one is left.
+    pub fn oxygen_rating(&self) -> AdventResult<u64> {
+        Ok(self.rating(RatingCriteria::OXYGEN)?.value)
+    }
+
+    /// Keeps the codes with the least common bit, or 0 on ties.
+    pub fn co2_rating(&self) -> AdventResult<u64> {
+        Ok(self.rating(RatingCriteria::CO2)?.value)
+    }
+
+    /// Filters the codes bit by bit with the criteria until one is left.
+    ///
+    /// A bit shared by all the candidates keeps them all. When several identical codes are
+    /// left after the last bit, the first one is the rating.
+    pub fn rating(&self, criteria: RatingCriteria) -> AdventResult<Rating> {
+        let mut candidates = self.codes.clone();
+        let mut trace = vec![];
+        let bits: Vec<u32> = match criteria.order {
+            BitOrder::MsbFirst => (0..self.width).rev().collect(),
+            BitOrder::LsbFirst => (0..self.width).collect(),
+        };
+        for bit in bits {
+            if candidates.len() <= 1 {
+                break;
+            }
+            let ones = count_ones(&candidates, bit);
+            let zeros = candidates.len() - ones;
+            if ones != 0 && zeros != 0 {
+                let keep_ones = match (criteria.keep, ones.cmp(&zeros)) {
+                    (_, Ordering::Equal) => criteria.tie_break,
+                    (Commonness::Most, order) => order == Ordering::Greater,
+                    (Commonness::Least, order) => order == Ordering::Less,
+                };
+                candidates.retain(|code| (code >> bit & 1 == 1) == keep_ones);
+            }
+            trace.push(candidates.len());
+        }
+        let value = candidates
+            .first()
+            .copied()
+            .ok_or(AdventError::InvalidData)?;
+        Ok(Rating { value, trace })
+    }
+}
+
+/// Which bit value a rating keeps at each step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Commonness {
+    Most,
+    Least,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+/// How [`DiagnosticReport::rating`] filters the codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingCriteria {
+    pub keep: Commonness,
+    /// Bit kept when there are as many ones as zeros.
+    pub tie_break: bool,
+    pub order: BitOrder,
+}
+
+impl RatingCriteria {
+    pub const OXYGEN: Self = Self {
+        keep: Commonness::Most,
+        tie_break: true,
+        order: BitOrder::MsbFirst,
+    };
+    pub const CO2: Self = Self {
+        keep: Commonness::Least,
+        tie_break: false,
+        order: BitOrder::MsbFirst,
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    pub value: u64,
+    /// Number of candidates left after each bit.
+    pub trace: Vec<usize>,
+}
+
+/// Number of codes with the bit set.
+fn count_ones(codes: &[u64], bit: u32) -> usize {
+    codes.iter().filter(|&&code| code >> bit & 1 == 1).count()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input<'a> = DiagnosticReport;
+    type Answer1 = Detailed<u128>;
+    type Answer2 = Detailed<u128>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        DiagnosticReport::parse(input)
+    }
+
+    fn part_one(report: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let (gamma, epsilon) = report.gamma_epsilon();
+        Ok(Detailed::new(gamma as u128 * epsilon as u128)
+            .with("gamma", gamma)
+            .with("epsilon", epsilon))
+    }
+
+    fn part_two(report: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let o2 = report.oxygen_rating()?;
+        let co2 = report.co2_rating()?;
+        Ok(Detailed::new(o2 as u128 * co2 as u128)
+            .with("o2", o2)
+            .with("co2", co2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{read_input, AdventError};
+
+    fn sample() -> DiagnosticReport {
+        let input = read_input(3, true).expect("Error reading data");
+        DiagnosticReport::parse(&input).expect("Failed to parse data.")
+    }
+
+    #[test]
+    fn validate_gamma_epsilon() {
+        let (gamma, epsilon) = sample().gamma_epsilon();
+        assert_eq!(gamma, 22);
+        assert_eq!(epsilon, 9);
+    }
+
+    #[test]
+    fn validate_o2_rating() {
+        let o2_rating = sample().oxygen_rating().expect("Failed to parse data.");
+        assert_eq!(o2_rating, 23);
+    }
+
+    #[test]
+    fn validate_co2_rating() {
+        let co2_rating = sample().co2_rating().expect("Failed to parse data.");
+        assert_eq!(co2_rating, 10);
+    }
+
+    #[test]
+    fn wide_codes() {
+        let ones = "1".repeat(64);
+        let leading_zero = format!("0{}", "1".repeat(63));
+        let report =
+            DiagnosticReport::parse(&format!("{}\n{}\n{}\n", ones, ones, leading_zero)).unwrap();
+        assert_eq!(report.width(), 64);
+        assert_eq!(report.gamma_epsilon(), (u64::MAX, 0));
+        assert_eq!(report.oxygen_rating().unwrap(), u64::MAX);
+        assert_eq!(report.co2_rating().unwrap(), u64::MAX >> 1);
+        assert!(DiagnosticReport::parse(&"1".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn rating_criteria() {
+        let report = sample();
+        let o2 = report.rating(RatingCriteria::OXYGEN).unwrap();
+        assert_eq!(o2.value, 23);
+        assert_eq!(o2.trace, vec![7, 4, 3, 2, 1]);
+        let co2 = report.rating(RatingCriteria::CO2).unwrap();
+        assert_eq!((co2.value, co2.trace), (10, vec![5, 2, 1]));
+        let lsb_first = RatingCriteria {
+            order: BitOrder::LsbFirst,
+            ..RatingCriteria::OXYGEN
+        };
+        let rating = report.rating(lsb_first).unwrap();
+        assert_eq!(rating.trace.last(), Some(&1));
+        // Every code shares the bit, none is dropped
+        let shared = DiagnosticReport::parse("11\n10\n").unwrap();
+        let co2 = shared.rating(RatingCriteria::CO2).unwrap();
+        assert_eq!((co2.value, co2.trace), (0b10, vec![2, 1]));
+    }
+
+    #[test]
+    fn invalid_digits() {
+        let error = DiagnosticReport::parse("1011\n10é1\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 2,
+                column: 3,
+                ref found,
+                ..
+            } if found == "é"
+        ));
+    }
+}
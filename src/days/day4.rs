@@ -0,0 +1,703 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::{input_lines, AdventError, AdventResult, Detailed, InputLine, Solution};
+
+#[derive(Debug, Default, Clone)]
+struct BoardNumber {
+    value: u32,
+    marked: bool,
+}
+
+impl BoardNumber {
+    fn new(value: u32) -> Self {
+        Self {
+            value,
+            marked: false,
+        }
+    }
+}
+
+/// Cells that win once they are all marked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinPattern {
+    Rows,
+    Columns,
+    /// Both diagonals, only square boards have them.
+    Diagonals,
+    FourCorners,
+    /// Every cell of the board.
+    Blackout,
+    /// Any set of `(column, row)` cells.
+    Cells(Vec<(usize, usize)>),
+}
+
+/// What the line that made a board win was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Row,
+    Column,
+    Diagonal,
+    FourCorners,
+    Blackout,
+    Cells,
+}
+
+/// A winning line, its cells being indices in the board numbers.
+#[derive(Debug, Clone)]
+struct Line {
+    kind: LineKind,
+    /// Row or column index, 0 for the diagonal from the top left and 1 for the other one,
+    /// and the index of the pattern in the rules for the others.
+    index: usize,
+    cells: Vec<usize>,
+}
+
+impl WinPattern {
+    fn lines(&self, index: usize, (columns, rows): (usize, usize)) -> AdventResult<Vec<Line>> {
+        let line = |kind, index, cells: Vec<usize>| Line { kind, index, cells };
+        let lines = match self {
+            WinPattern::Rows => (0..rows)
+                .map(|y| {
+                    line(
+                        LineKind::Row,
+                        y,
+                        (0..columns).map(|x| y * columns + x).collect(),
+                    )
+                })
+                .collect(),
+            WinPattern::Columns => (0..columns)
+                .map(|x| {
+                    line(
+                        LineKind::Column,
+                        x,
+                        (0..rows).map(|y| y * columns + x).collect(),
+                    )
+                })
+                .collect(),
+            WinPattern::Diagonals if columns == rows => vec![
+                line(
+                    LineKind::Diagonal,
+                    0,
+                    (0..rows).map(|i| i * columns + i).collect(),
+                ),
+                line(
+                    LineKind::Diagonal,
+                    1,
+                    (0..rows).map(|i| i * columns + columns - 1 - i).collect(),
+                ),
+            ],
+            WinPattern::Diagonals => vec![],
+            WinPattern::FourCorners => {
+                let mut cells = vec![0, columns - 1, (rows - 1) * columns, rows * columns - 1];
+                cells.sort_unstable();
+                cells.dedup();
+                vec![line(LineKind::FourCorners, index, cells)]
+            }
+            WinPattern::Blackout => vec![line(
+                LineKind::Blackout,
+                index,
+                (0..rows * columns).collect(),
+            )],
+            WinPattern::Cells(positions) => {
+                let cells = positions
+                    .iter()
+                    .map(|&(x, y)| {
+                        (x < columns && y < rows)
+                            .then_some(y * columns + x)
+                            .ok_or_else(|| {
+                                AdventError::InvalidArgument(format!(
+                                    "cell {},{} is outside of a {}x{} board",
+                                    x, y, columns, rows
+                                ))
+                            })
+                    })
+                    .collect::<AdventResult<_>>()?;
+                vec![line(LineKind::Cells, index, cells)]
+            }
+        };
+        Ok(lines)
+    }
+}
+
+/// How the game is played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Checked in order, the first complete line is the winning one.
+    pub patterns: Vec<WinPattern>,
+    /// The centre cell of boards with an odd number of rows and columns is marked from the
+    /// start.
+    pub free_centre: bool,
+}
+
+/// Full rows or columns, as in the puzzle.
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            patterns: vec![WinPattern::Rows, WinPattern::Columns],
+            free_centre: false,
+        }
+    }
+}
+
+impl Display for LineKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            LineKind::Row => "row",
+            LineKind::Column => "column",
+            LineKind::Diagonal => "diagonal",
+            LineKind::FourCorners => "four corners",
+            LineKind::Blackout => "blackout",
+            LineKind::Cells => "cells",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How a board won.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Winner {
+    /// Sum of the unmarked numbers times the last number drawn, wide enough not to overflow.
+    pub score: u128,
+    /// Values of the completed line.
+    pub line: Vec<u32>,
+    /// See [`LineKind`], the row or column index for rows and columns, from 0.
+    pub line_index: usize,
+    pub line_kind: LineKind,
+}
+
+/// Outcome of marking a number on a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Marked {
+    Missed,
+    Hit,
+    Won,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Board {
+    number: usize,
+    rank: u32,
+    /// Columns and rows.
+    size: (usize, usize),
+    numbers: Vec<BoardNumber>,
+    lines: Vec<Line>,
+    /// Lines going through each cell.
+    cell_lines: Vec<Vec<usize>>,
+    /// Unmarked cells left on each line.
+    remaining: Vec<usize>,
+    /// First complete line, in the order of the rules.
+    complete: Option<usize>,
+    unmarked_sum: u64,
+    winner: Option<Winner>,
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, number) in self.numbers.iter().enumerate() {
+            if i % self.size.0 == 0 && i != 0 {
+                writeln!(f)?;
+            }
+            if number.marked {
+                write!(f, "*")?;
+            } else {
+                write!(f, " ")?;
+            }
+            write!(f, "{:02} ", number.value)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Board {
+    fn new(number: usize, size: (usize, usize), numbers: Vec<BoardNumber>) -> Self {
+        Self {
+            number,
+            rank: 0,
+            size,
+            numbers,
+            lines: vec![],
+            cell_lines: vec![],
+            remaining: vec![],
+            complete: None,
+            unmarked_sum: 0,
+            winner: None,
+        }
+    }
+
+    /// Checks the board has the expected size and no value twice.
+    fn check(&self, expected_size: Option<(usize, usize)>) -> AdventResult<()> {
+        let error = |problem: String| AdventError::InvalidBoard {
+            day: Day4::DAY,
+            board: self.number + 1,
+            problem,
+        };
+        let (columns, rows) = self.size;
+        if let Some(expected @ (expected_columns, expected_rows)) = expected_size {
+            if self.size != expected {
+                return Err(error(format!(
+                    "{}x{} numbers, expected {}x{} like the first board",
+                    columns, rows, expected_columns, expected_rows
+                )));
+            }
+        }
+        let mut seen = HashSet::new();
+        if let Some(number) = self.numbers.iter().find(|n| !seen.insert(n.value)) {
+            return Err(error(format!("{} appears more than once", number.value)));
+        }
+        Ok(())
+    }
+
+    /// Sets the lines of the rules up, with every cell unmarked but the free one.
+    fn apply(&mut self, rules: &Rules) -> AdventResult<()> {
+        self.lines.clear();
+        for (index, pattern) in rules.patterns.iter().enumerate() {
+            self.lines.extend(pattern.lines(index, self.size)?);
+        }
+        self.cell_lines = vec![vec![]; self.numbers.len()];
+        for (id, line) in self.lines.iter().enumerate() {
+            for &cell in &line.cells {
+                self.cell_lines[cell].push(id);
+            }
+        }
+        self.remaining = self.lines.iter().map(|line| line.cells.len()).collect();
+        self.complete = self.remaining.iter().position(|&count| count == 0);
+        self.unmarked_sum = 0;
+        for number in self.numbers.iter_mut() {
+            number.marked = false;
+            self.unmarked_sum += number.value as u64;
+        }
+        let (columns, rows) = self.size;
+        if rules.free_centre && columns % 2 == 1 && rows % 2 == 1 {
+            self.mark_cell(rows / 2 * columns + columns / 2);
+        }
+        Ok(())
+    }
+
+    fn mark_cell(&mut self, cell: usize) {
+        let number = &mut self.numbers[cell];
+        if number.marked {
+            return;
+        }
+        number.marked = true;
+        self.unmarked_sum -= number.value as u64;
+        for &line in &self.cell_lines[cell] {
+            self.remaining[line] -= 1;
+            if self.remaining[line] == 0 && self.complete.is_none_or(|first| line < first) {
+                self.complete = Some(line);
+            }
+        }
+    }
+
+    /// Marks the cells holding the drawn number, the board wins with the first complete line.
+    fn mark(&mut self, cells: &[usize], drawn_number: u32) -> Marked {
+        if cells.is_empty() {
+            return Marked::Missed;
+        }
+        for &cell in cells {
+            self.mark_cell(cell);
+        }
+        let Some(line) = self.complete.map(|line| &self.lines[line]) else {
+            return Marked::Hit;
+        };
+        self.winner = Some(Winner {
+            score: self.unmarked_sum as u128 * drawn_number as u128,
+            line: line
+                .cells
+                .iter()
+                .map(|&cell| self.numbers[cell].value)
+                .collect(),
+            line_index: line.index,
+            line_kind: line.kind,
+        });
+        Marked::Won
+    }
+}
+
+/// How [`Bingo::parse_input`] checks the boards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// At least one board, all of the same size, with no value twice on a board.
+    Strict,
+    /// Boards may differ in size and repeat values.
+    Lenient,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Bingo {
+    boards: Vec<Board>,
+    numbers: Vec<u32>,
+    /// Where each value is, as board and cell indices sorted by board.
+    index: HashMap<u32, Vec<(usize, usize)>>,
+}
+
+impl Bingo {
+    fn new(boards: Vec<Board>, numbers: Vec<u32>) -> AdventResult<Self> {
+        let mut index = HashMap::<u32, Vec<(usize, usize)>>::new();
+        for (b, board) in boards.iter().enumerate() {
+            for (cell, number) in board.numbers.iter().enumerate() {
+                index.entry(number.value).or_default().push((b, cell));
+            }
+        }
+        Self {
+            boards,
+            numbers,
+            index,
+        }
+        .with_rules(&Rules::default())
+    }
+
+    /// Plays with other rules, the patterns must fit on every board.
+    pub fn with_rules(mut self, rules: &Rules) -> AdventResult<Self> {
+        for board in self.boards.iter_mut() {
+            board.apply(rules)?;
+        }
+        Ok(self)
+    }
+
+    /// Reads the drawn numbers then the boards, separated by any number of blank lines.
+    pub fn parse_input(input: &str, mode: ParseMode) -> AdventResult<Bingo> {
+        // For what is missing, on the line after the last one
+        let end = InputLine {
+            day: Day4::DAY,
+            number: input.lines().count() + 1,
+            text: "",
+        };
+        let mut lines = input_lines(Day4::DAY, input).skip_while(|l| l.text.trim().is_empty());
+        // Read drawn numbers
+        let line = lines
+            .next()
+            .ok_or_else(|| end.error_at_end("the drawn numbers"))?;
+        let drawn_numbers = line
+            .text
+            .trim()
+            .split(',')
+            .map(|n| line.parse::<u32>(n, "a drawn number"))
+            .collect::<AdventResult<Vec<u32>>>()?;
+        // Group the rows of each board
+        let mut blocks = vec![vec![]];
+        for line in lines {
+            if !line.text.trim().is_empty() {
+                blocks.last_mut().unwrap().push(line);
+            } else if !blocks.last().unwrap().is_empty() {
+                blocks.push(vec![]);
+            }
+        }
+        blocks.retain(|block| !block.is_empty());
+
+        let mut boards = Vec::<Board>::new();
+        for (number, block) in blocks.iter().enumerate() {
+            let mut board_numbers = Vec::<BoardNumber>::new();
+            let mut columns = 0;
+            for line in block {
+                let clean_buffer = line.text.trim();
+                let mut numbers = clean_buffer
+                    .split_whitespace()
+                    .map(|n| Ok(BoardNumber::new(line.parse::<u32>(n, "a board number")?)))
+                    .collect::<AdventResult<Vec<BoardNumber>>>()?;
+                if columns == 0 {
+                    columns = numbers.len();
+                } else if numbers.len() != columns {
+                    return Err(line.error(clean_buffer, format!("{} board numbers", columns)));
+                }
+                board_numbers.append(&mut numbers);
+            }
+            let board = Board::new(number, (columns, block.len()), board_numbers);
+            if mode == ParseMode::Strict {
+                board.check(boards.first().map(|first| first.size))?;
+            }
+            boards.push(board);
+        }
+        if mode == ParseMode::Strict && boards.is_empty() {
+            return Err(end.error_at_end("a board"));
+        }
+        Self::new(boards, drawn_numbers)
+    }
+
+    /// Plays the game one drawn number at a time, the boards of `self` are left untouched.
+    pub fn replay(&self) -> Replay<'_> {
+        Replay {
+            boards: self.boards.clone(),
+            numbers: self.numbers.iter(),
+            index: &self.index,
+            rank: 1,
+        }
+    }
+
+    /// Plays the whole game, returning the boards with their rank and how they won.
+    fn rank_winners(&self) -> Vec<Board> {
+        let mut replay = self.replay();
+        replay.by_ref().for_each(drop);
+        replay.boards
+    }
+}
+
+fn first_winner(boards: &[Board]) -> Option<&Board> {
+    boards.iter().find(|b| b.rank == 1)
+}
+
+fn last_winner(boards: &[Board]) -> Option<&Board> {
+    boards
+        .iter()
+        .filter(|b| b.winner.is_some())
+        .max_by_key(|b| b.rank)
+}
+
+/// What happened when a number was drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawEvent {
+    pub number: u32,
+    /// Boards still playing that had the number, by their position in the input from 0.
+    pub marked: Vec<usize>,
+    /// Boards that won with this number, they stop playing.
+    pub won: Vec<(usize, Winner)>,
+}
+
+/// The game being played, see [`Bingo::replay`].
+#[derive(Debug, Clone)]
+pub struct Replay<'a> {
+    boards: Vec<Board>,
+    numbers: std::slice::Iter<'a, u32>,
+    index: &'a HashMap<u32, Vec<(usize, usize)>>,
+    rank: u32,
+}
+
+impl Iterator for Replay<'_> {
+    type Item = DrawEvent;
+
+    fn next(&mut self) -> Option<DrawEvent> {
+        let number = *self.numbers.next()?;
+        let mut event = DrawEvent {
+            number,
+            marked: vec![],
+            won: vec![],
+        };
+        let hits = self.index.get(&number).map_or(&[][..], Vec::as_slice);
+        for board_hits in hits.chunk_by(|a, b| a.0 == b.0) {
+            let board = &mut self.boards[board_hits[0].0];
+            if board.winner.is_some() {
+                continue;
+            }
+            let cells: Vec<usize> = board_hits.iter().map(|&(_, cell)| cell).collect();
+            match board.mark(&cells, number) {
+                Marked::Missed => continue,
+                Marked::Hit => {}
+                Marked::Won => {
+                    board.rank = self.rank;
+                    self.rank += 1;
+                    event
+                        .won
+                        .extend(board.winner.clone().map(|w| (board.number, w)));
+                }
+            }
+            event.marked.push(board.number);
+        }
+        Some(event)
+    }
+}
+
+/// The answer of a part, with how the board won. Boards and lines count from 1 as in the
+/// errors, unlike the indexes of [`Winner`] and [`DrawEvent`].
+fn detailed(board: &Board) -> AdventResult<Detailed<u128>> {
+    let winner = board.winner.as_ref().ok_or(AdventError::InvalidData)?;
+    Ok(Detailed::new(winner.score)
+        .with("board", board.number + 1)
+        .with("rank", board.rank)
+        .with("line_kind", winner.line_kind.to_string().as_str())
+        .with("line_index", winner.line_index + 1)
+        .with("line", winner.line.clone()))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Bingo;
+    type Answer1 = Detailed<u128>;
+    type Answer2 = Detailed<u128>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        Bingo::parse_input(input, ParseMode::Strict)
+    }
+
+    fn part_one(bingo: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        detailed(first_winner(&bingo.rank_winners()).ok_or(AdventError::InvalidData)?)
+    }
+
+    fn part_two(bingo: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        detailed(last_winner(&bingo.rank_winners()).ok_or(AdventError::InvalidData)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, Detail};
+
+    /// Plays the sample with the rules, returning the first winner.
+    fn first_win(rules: &Rules) -> Winner {
+        let input = read_input(4, true).unwrap();
+        let bingo = Bingo::parse_input(&input, ParseMode::Strict)
+            .unwrap()
+            .with_rules(rules)
+            .unwrap();
+        let boards = bingo.rank_winners();
+        first_winner(&boards).unwrap().winner.clone().unwrap()
+    }
+
+    #[test]
+    fn puzzle_rules() {
+        let winner = first_win(&Rules::default());
+        assert_eq!(
+            winner,
+            Winner {
+                score: 4512,
+                line: vec![14, 21, 17, 24, 4],
+                line_index: 0,
+                line_kind: LineKind::Row,
+            }
+        );
+        let input = read_input(4, true).unwrap();
+        let bingo = Bingo::parse_input(&input, ParseMode::Strict).unwrap();
+        let details = Day4::part_one(&bingo).unwrap().details;
+        assert!(details.contains(&("board", Detail::Int(3))));
+        assert!(details.contains(&("line_index", Detail::Int(1))));
+    }
+
+    #[test]
+    fn diagonals_and_free_centre() {
+        let rules = Rules {
+            patterns: vec![WinPattern::Diagonals],
+            free_centre: true,
+        };
+        let winner = first_win(&rules);
+        assert_eq!(winner.line_kind, LineKind::Diagonal);
+        assert_eq!(winner.line.len(), 5);
+        assert!(winner.line_index < 2);
+        let rules = Rules {
+            patterns: vec![WinPattern::Cells(vec![(2, 2)])],
+            free_centre: true,
+        };
+        assert_eq!(first_win(&rules).line.len(), 1);
+    }
+
+    #[test]
+    fn custom_patterns() {
+        let rules = Rules {
+            patterns: vec![WinPattern::FourCorners],
+            ..Rules::default()
+        };
+        assert_eq!(first_win(&rules).line_kind, LineKind::FourCorners);
+        let rules = Rules {
+            patterns: vec![WinPattern::Blackout],
+            ..Rules::default()
+        };
+        assert_eq!(first_win(&rules).line.len(), 25);
+        let rules = Rules {
+            patterns: vec![WinPattern::Cells(vec![(5, 0)])],
+            ..Rules::default()
+        };
+        let input = read_input(4, true).unwrap();
+        assert!(Bingo::parse_input(&input, ParseMode::Strict)
+            .unwrap()
+            .with_rules(&rules)
+            .is_err());
+    }
+
+    #[test]
+    fn non_square_boards() {
+        let bingo = Bingo::parse_input("3,1,2\n\n1 2 3\n4 5 6\n", ParseMode::Strict).unwrap();
+        let boards = bingo.rank_winners();
+        let winner = first_winner(&boards).unwrap().winner.clone().unwrap();
+        assert_eq!(winner.score, 15 * 2);
+        assert_eq!(winner.line, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn repeated_values() {
+        // The 7 is twice on the second board, one draw completes its row
+        let bingo =
+            Bingo::parse_input("7,9\n\n9 7\n2 3\n\n7 7\n8 9\n", ParseMode::Lenient).unwrap();
+        let boards = bingo.rank_winners();
+        let winner = first_winner(&boards).unwrap();
+        assert_eq!(winner.number, 1);
+        assert_eq!(winner.winner.as_ref().unwrap().score, (8 + 9) * 7);
+        let last = last_winner(&boards).unwrap();
+        assert_eq!((last.number, last.rank), (0, 2));
+        assert_eq!(last.winner.as_ref().unwrap().score, (2 + 3) * 9);
+    }
+
+    #[test]
+    fn large_values() {
+        let input = "7,3\n\n7 3000000000\n3 4\n";
+        let bingo = Bingo::parse_input(input, ParseMode::Strict).unwrap();
+        assert_eq!(Day4::part_one(&bingo).unwrap().answer, (3000000000 + 4) * 3);
+        // The score is past u64
+        let input = "4000000000\n\n4000000000 4000000000\n4000000001 4000000002\n\
+                     4000000003 4000000004\n";
+        let bingo = Bingo::parse_input(input, ParseMode::Lenient).unwrap();
+        let score = Day4::part_one(&bingo).unwrap().answer;
+        assert_eq!(score, (4 * 4000000000 + 1 + 2 + 3 + 4) * 4000000000);
+        assert!(score > u64::MAX as u128);
+    }
+
+    #[test]
+    fn parse_modes() {
+        let input = "\n1,2\n\n\n1 2  \n3 4\n\n\n\n5 6\n7 8\n\n\n";
+        let bingo = Bingo::parse_input(input, ParseMode::Strict).unwrap();
+        assert_eq!(bingo.boards.len(), 2);
+        assert_eq!(bingo.boards[1].size, (2, 2));
+        // Boards of different sizes, then with a repeated value
+        for input in ["1\n\n1 2\n3 4\n\n5 6 7\n", "1\n\n1 2\n3 4\n\n5 6\n6 7\n"] {
+            let error = Bingo::parse_input(input, ParseMode::Strict).unwrap_err();
+            assert!(matches!(error, AdventError::InvalidBoard { board: 2, .. }));
+            let bingo = Bingo::parse_input(input, ParseMode::Lenient).unwrap();
+            assert_eq!(bingo.boards.len(), 2);
+        }
+        // Rows must have the same length in both modes
+        for mode in [ParseMode::Strict, ParseMode::Lenient] {
+            let error = Bingo::parse_input("1\n\n1 2\n3\n", mode).unwrap_err();
+            assert!(matches!(error, AdventError::Parse { line: 4, .. }));
+        }
+        let error = Bingo::parse_input("1\n\n\n", ParseMode::Strict).unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 4,
+                column: 1,
+                ..
+            }
+        ));
+        let error = Bingo::parse_input("\n \n", ParseMode::Lenient).unwrap_err();
+        assert!(
+            matches!(error, AdventError::Parse { line: 3, ref expected, .. }
+            if expected == "the drawn numbers")
+        );
+        let bingo = Bingo::parse_input("1\n\n\n", ParseMode::Lenient).unwrap();
+        assert!(bingo.boards.is_empty());
+    }
+
+    #[test]
+    fn replay_events() {
+        let input = read_input(4, true).unwrap();
+        let bingo = Bingo::parse_input(&input, ParseMode::Strict).unwrap();
+        let events: Vec<_> = bingo.replay().collect();
+        assert_eq!(events.len(), 27);
+        assert_eq!(events[0].number, 7);
+        assert_eq!(events[0].marked, vec![0, 1, 2]);
+        let won: Vec<_> = events
+            .iter()
+            .flat_map(|e| e.won.iter().map(move |(board, _)| (e.number, *board)))
+            .collect();
+        assert_eq!(won, vec![(24, 2), (16, 0), (13, 1)]);
+        // Boards stop marking once they won
+        assert!(events
+            .iter()
+            .skip_while(|e| e.number != 13)
+            .skip(1)
+            .all(|e| e.marked.is_empty()));
+    }
+}
@@ -0,0 +1,672 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    io::Write,
+};
+
+use crate::{
+    grid::Grid2D,
+    image::{self, Svg},
+    input_lines, log_enabled, AdventResult, Detailed, Part, Solution, Variant, Verbosity,
+};
+
+/// Simple point, since it's just i32, we are going to allow Copy and Clone.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+/// Just to get beauty prints.
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Line segment
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LineSegment {
+    p1: Point,
+    p2: Point,
+}
+
+/// Just to get beauty prints.
+impl Display for LineSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.p1, self.p2)
+    }
+}
+
+impl LineSegment {
+    fn new(p1: Point, p2: Point) -> Self {
+        Self { p1, p2 }
+    }
+
+    fn is_orthogonal(&self) -> bool {
+        self.p1.x == self.p2.x || self.p1.y == self.p2.y
+    }
+
+    /// Whether the segment is horizontal, vertical or at 45°.
+    fn is_straight(&self) -> bool {
+        self.run().is_some()
+    }
+
+    /// The direction and the line of the segment, with the positions of its ends along it,
+    /// `None` unless it is orthogonal or at 45°.
+    fn run(&self) -> Option<(Direction, i64, i64, i64)> {
+        let (x1, y1) = (self.p1.x as i64, self.p1.y as i64);
+        let (x2, y2) = (self.p2.x as i64, self.p2.y as i64);
+        let direction = if y1 == y2 {
+            Direction::Horizontal
+        } else if x1 == x2 {
+            Direction::Vertical
+        } else if x2 - x1 == y2 - y1 {
+            Direction::Diagonal
+        } else if x2 - x1 == y1 - y2 {
+            Direction::AntiDiagonal
+        } else {
+            return None;
+        };
+        let (start, end) = (direction.position(x1, y1), direction.position(x2, y2));
+        Some((
+            direction,
+            direction.key(x1, y1),
+            start.min(end),
+            start.max(end),
+        ))
+    }
+
+    /// This uses the full algorithm for Bresenham lines
+    ///
+    /// [Wikipedia](https://en.wikipedia.org/wiki/Bresenham's_line_algorithm)
+    fn bresenham_line_points(&self) -> Vec<Point> {
+        let dx = (self.p2.x as i64 - self.p1.x as i64).abs();
+        let dy = -(self.p2.y as i64 - self.p1.y as i64).abs();
+        let mut err = dx + dy;
+        let mut x = self.p1.x;
+        let mut y = self.p1.y;
+
+        let mut points = vec![];
+        loop {
+            points.push(Point { x, y });
+            if x == self.p2.x && y == self.p2.y {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                if self.p1.x < self.p2.x {
+                    x += 1;
+                } else {
+                    x -= 1;
+                }
+            }
+            if e2 <= dx {
+                err += dx;
+                if self.p1.y < self.p2.y {
+                    y += 1;
+                } else {
+                    y -= 1;
+                }
+            }
+        }
+        points
+    }
+}
+
+/// The segments taken into account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentFilter {
+    /// Only the horizontal and vertical ones, as in part 1.
+    Orthogonal,
+    /// Also the ones at 45°, as in part 2.
+    Diagonal,
+    /// Every segment, traced with Bresenham's algorithm.
+    Any,
+}
+
+impl SegmentFilter {
+    pub fn accepts(self, segment: &LineSegment) -> bool {
+        match self {
+            SegmentFilter::Orthogonal => segment.is_orthogonal(),
+            SegmentFilter::Diagonal => segment.is_straight(),
+            SegmentFilter::Any => true,
+        }
+    }
+}
+
+/// The directions the sweep handles. The points of a line share a key, and are ordered by
+/// their position along it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Horizontal,
+        Direction::Vertical,
+        Direction::Diagonal,
+        Direction::AntiDiagonal,
+    ];
+
+    fn key(self, x: i64, y: i64) -> i64 {
+        match self {
+            Direction::Horizontal => y,
+            Direction::Vertical => x,
+            Direction::Diagonal => y - x,
+            Direction::AntiDiagonal => x + y,
+        }
+    }
+
+    fn position(self, x: i64, y: i64) -> i64 {
+        match self {
+            Direction::Vertical => y,
+            _ => x,
+        }
+    }
+
+    fn point(self, key: i64, position: i64) -> (i64, i64) {
+        match self {
+            Direction::Horizontal => (position, key),
+            Direction::Vertical => (key, position),
+            Direction::Diagonal => (position, position + key),
+            Direction::AntiDiagonal => (position, key - position),
+        }
+    }
+}
+
+/// Consecutive points of a line covered by the same number of segments.
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    direction: Direction,
+    key: i64,
+    start: i64,
+    end: i64,
+    count: u32,
+}
+
+impl Run {
+    fn len(&self) -> u64 {
+        (self.end - self.start + 1) as u64
+    }
+}
+
+/// Counts how many segments cover each point without visiting the points, so the coordinates
+/// can be in the millions.
+///
+/// The segments of a line are merged into runs of equal coverage, and the only points covered
+/// by several directions are where their runs cross. Segments at other angles are traced
+/// point by point, each one a horizontal run.
+#[derive(Debug, Default)]
+pub struct Sweep {
+    runs: Vec<Run>,
+    /// Coverage of each direction where runs cross.
+    crossings: HashMap<(i64, i64), [u32; 4]>,
+}
+
+impl Sweep {
+    pub fn new(segments: &[LineSegment], filter: SegmentFilter) -> Self {
+        let mut lines = HashMap::<(usize, i64), Vec<(i64, i32)>>::new();
+        let mut add = |direction: Direction, key, start, end| {
+            let events = lines.entry((direction as usize, key)).or_default();
+            events.push((start, 1));
+            events.push((end + 1, -1));
+        };
+        for segment in segments.iter().filter(|s| filter.accepts(s)) {
+            match segment.run() {
+                Some((direction, key, start, end)) => add(direction, key, start, end),
+                None => {
+                    for point in segment.bresenham_line_points() {
+                        let x = point.x as i64;
+                        add(Direction::Horizontal, point.y as i64, x, x);
+                    }
+                }
+            }
+        }
+        let mut sweep = Self::default();
+        for ((direction, key), mut events) in lines {
+            events.sort_unstable();
+            let mut count = 0;
+            let mut previous = 0;
+            for (position, delta) in events {
+                if count > 0 && position > previous {
+                    sweep.runs.push(Run {
+                        direction: Direction::ALL[direction],
+                        key,
+                        start: previous,
+                        end: position - 1,
+                        count: count as u32,
+                    });
+                }
+                count += delta;
+                previous = position;
+            }
+        }
+        sweep.cross();
+        sweep
+    }
+
+    /// Finds where the runs of different directions cross.
+    fn cross(&mut self) {
+        let mut by_key = [(); 4].map(|_| BTreeMap::<i64, Vec<usize>>::new());
+        for (index, run) in self.runs.iter().enumerate() {
+            by_key[run.direction as usize]
+                .entry(run.key)
+                .or_default()
+                .push(index);
+        }
+        for run in &self.runs {
+            for other in Direction::ALL.into_iter().skip(run.direction as usize + 1) {
+                // The key of the other direction changes by `step` at each point of the run
+                let key_at = |position| {
+                    let (x, y) = run.direction.point(run.key, position);
+                    other.key(x, y)
+                };
+                let step = key_at(1) - key_at(0);
+                let (first, last) = (key_at(run.start), key_at(run.end));
+                for (&key, crossed) in
+                    by_key[other as usize].range(first.min(last)..=first.max(last))
+                {
+                    if (key - first) % step != 0 {
+                        continue;
+                    }
+                    let (x, y) = run
+                        .direction
+                        .point(run.key, run.start + (key - first) / step);
+                    let position = other.position(x, y);
+                    for &index in crossed {
+                        let crossed = &self.runs[index];
+                        if (crossed.start..=crossed.end).contains(&position) {
+                            let counts = self.crossings.entry((x, y)).or_default();
+                            counts[run.direction as usize] = run.count;
+                            counts[other as usize] = crossed.count;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Points covered by `threshold` segments or more, and at least one.
+    pub fn overlaps_at_least(&self, threshold: u32) -> u64 {
+        let mut total: u64 = self
+            .runs
+            .iter()
+            .filter(|run| run.count >= threshold)
+            .map(Run::len)
+            .sum();
+        // The runs counted the crossings once per direction
+        for counts in self.crossings.values() {
+            total -= counts.iter().filter(|&&c| c > 0 && c >= threshold).count() as u64;
+            if counts.iter().sum::<u32>() >= threshold {
+                total += 1;
+            }
+        }
+        total
+    }
+
+    /// Points covered by two segments or more, like [`Grid::overlaps`].
+    pub fn overlaps(&self) -> u64 {
+        self.overlaps_at_least(2)
+    }
+
+    /// How many points are covered by each number of segments, from one.
+    pub fn histogram(&self) -> BTreeMap<u32, u64> {
+        let mut histogram = BTreeMap::<u32, u64>::new();
+        for run in &self.runs {
+            *histogram.entry(run.count).or_default() += run.len();
+        }
+        for counts in self.crossings.values() {
+            for &count in counts.iter().filter(|&&c| c > 0) {
+                *histogram.entry(count).or_default() -= 1;
+            }
+            *histogram.entry(counts.iter().sum()).or_default() += 1;
+        }
+        histogram.retain(|_, &mut points| points > 0);
+        histogram
+    }
+}
+
+/// Our grid where we store the lines and overlap information.
+#[derive(Debug, Default)]
+pub struct Grid {
+    lines: Vec<LineSegment>,
+    /// The point at the top left of the accumulator.
+    origin: Point,
+    accumulator: Grid2D<u32>,
+}
+
+impl Grid {
+    /// An empty grid large enough for the segments.
+    fn new(segments: &[LineSegment]) -> Self {
+        let points = || segments.iter().flat_map(|line| [line.p1, line.p2]);
+        let origin = Point {
+            x: points().map(|p| p.x).min().unwrap_or(0),
+            y: points().map(|p| p.y).min().unwrap_or(0),
+        };
+        let width = points().map(|p| (p.x - origin.x) as usize + 1).max();
+        let height = points().map(|p| (p.y - origin.y) as usize + 1).max();
+        Self {
+            lines: vec![],
+            origin,
+            accumulator: Grid2D::filled(width.unwrap_or(0), height.unwrap_or(0), 0),
+        }
+    }
+
+    fn insert_line(&mut self, line: LineSegment) {
+        self.trace_lines(&line);
+        self.lines.push(line);
+    }
+
+    fn trace_lines(&mut self, line: &LineSegment) {
+        let points = line.bresenham_line_points();
+        for point in points {
+            self.mark(point);
+        }
+    }
+
+    fn mark(&mut self, point: Point) {
+        let x = (point.x - self.origin.x) as usize;
+        let y = (point.y - self.origin.y) as usize;
+        self.accumulator[(x, y)] += 1;
+    }
+
+    /// Points covered by `threshold` segments or more, and at least one.
+    pub fn overlaps_at_least(&self, threshold: u32) -> u64 {
+        let threshold = threshold.max(1);
+        self.accumulator
+            .iter()
+            .filter(|(_, &v)| v >= threshold)
+            .count() as u64
+    }
+
+    pub fn overlaps(&self) -> u64 {
+        self.overlaps_at_least(2)
+    }
+
+    /// How many points are covered by each number of segments, from one.
+    pub fn histogram(&self) -> BTreeMap<u32, u64> {
+        let mut histogram = BTreeMap::<u32, u64>::new();
+        for (_, &count) in self.accumulator.iter().filter(|(_, &v)| v > 0) {
+            *histogram.entry(count).or_default() += 1;
+        }
+        histogram
+    }
+
+    /// Greyscale heatmap of the coverage.
+    pub fn write_pgm(&self, out: impl Write) -> AdventResult<()> {
+        Ok(image::write_pgm(out, &self.accumulator)?)
+    }
+
+    /// Colour heatmap of the coverage.
+    pub fn write_ppm(&self, out: impl Write) -> AdventResult<()> {
+        Ok(image::write_ppm(out, &self.accumulator)?)
+    }
+
+    /// Drawing of the segments, the points covered `threshold` times or more in red.
+    pub fn write_svg(&self, out: impl Write, threshold: u32) -> AdventResult<()> {
+        let (x0, y0) = (self.origin.x as i64, self.origin.y as i64);
+        let mut svg = Svg::new(
+            x0,
+            y0,
+            self.accumulator.width() as u64,
+            self.accumulator.height() as u64,
+        );
+        for line in &self.lines {
+            let (p1, p2) = (line.p1, line.p2);
+            svg.line(
+                (p1.x as i64, p1.y as i64),
+                (p2.x as i64, p2.y as i64),
+                "steelblue",
+            );
+        }
+        // One rectangle per run of highlighted points in a row
+        let threshold = threshold.max(1);
+        for (y, row) in self.accumulator.rows().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let length = row[x..].iter().take_while(|&&c| c >= threshold).count();
+                if length > 0 {
+                    svg.rect(x0 + x as i64, y0 + y as i64, length as u64, 1, "red");
+                }
+                x += length.max(1);
+            }
+        }
+        Ok(svg.write(out)?)
+    }
+
+    fn draw_lines_data(&self) {
+        for line in &self.lines {
+            eprint!("{} [", &line);
+            for point in &line.bresenham_line_points() {
+                eprint!("({}),", point);
+            }
+            eprintln!("]");
+        }
+    }
+
+    fn draw_grid(&self) {
+        for row in self.accumulator.rows() {
+            for &count in row {
+                if count == 0 {
+                    eprint!(".");
+                } else {
+                    eprint!("{}", count);
+                }
+            }
+            eprintln!();
+        }
+    }
+}
+
+/// Parses the line segments, one per line in the form `x1,y1 -> x2,y2`.
+fn parse_input(input: &str) -> AdventResult<Vec<LineSegment>> {
+    let mut segments = vec![];
+    for line in input_lines(Day5::DAY, input) {
+        let clean_buffer = line.text.trim();
+        if clean_buffer.is_empty() {
+            continue;
+        }
+        let (p1, p2) = clean_buffer
+            .split_once("->")
+            .ok_or_else(|| line.error_at_end("'->'"))?;
+        let parse_point = |p: &str| -> AdventResult<Point> {
+            let p = p.trim();
+            let (x, y) = p.split_once(',').ok_or_else(|| line.error(p, "x,y"))?;
+            Ok(Point {
+                x: line.parse(x, "a coordinate")?,
+                y: line.parse(y, "a coordinate")?,
+            })
+        };
+        segments.push(LineSegment::new(parse_point(p1)?, parse_point(p2)?));
+    }
+    Ok(segments)
+}
+
+/// Adds the lines the filter accepts to a grid just large enough for them.
+pub fn build_grid(segments: &[LineSegment], filter: SegmentFilter) -> Grid {
+    let segments: Vec<LineSegment> = segments
+        .iter()
+        .filter(|s| filter.accepts(s))
+        .copied()
+        .collect();
+    let mut grid = Grid::new(&segments);
+    for line in segments {
+        grid.insert_line(line);
+    }
+    grid
+}
+
+/// Points covered twice or more, counted with the sweep so large maps fit in memory.
+fn overlaps(segments: &[LineSegment], filter: SegmentFilter) -> Detailed<u64> {
+    if log_enabled(Verbosity::Trace) {
+        // The drawing needs every point of the map
+        let grid = build_grid(segments, filter);
+        grid.draw_lines_data();
+        grid.draw_grid();
+    }
+    let sweep = Sweep::new(segments, filter);
+    let max_overlap = sweep.histogram().keys().last().copied().unwrap_or(0);
+    Detailed::new(sweep.overlaps())
+        .with(
+            "segments",
+            segments.iter().filter(|s| filter.accepts(s)).count(),
+        )
+        .with("max_overlap", max_overlap)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Vec<LineSegment>;
+    type Answer1 = Detailed<u64>;
+    type Answer2 = Detailed<u64>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(segments: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        Ok(overlaps(segments, SegmentFilter::Orthogonal))
+    }
+
+    fn part_two(segments: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        Ok(overlaps(segments, SegmentFilter::Diagonal))
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![
+            Variant {
+                name: "grid",
+                part: Part::One,
+                solve: |segments| {
+                    let grid = build_grid(segments, SegmentFilter::Orthogonal);
+                    Ok(grid.overlaps().to_string())
+                },
+            },
+            Variant {
+                name: "grid",
+                part: Part::Two,
+                solve: |segments| {
+                    let grid = build_grid(segments, SegmentFilter::Diagonal);
+                    Ok(grid.overlaps().to_string())
+                },
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, AdventError};
+
+    #[test]
+    fn invalid_line() {
+        let error = parse_input("0,9 -> 5,9\n8,0 -> 0\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 2,
+                column: 8,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn sweep_matches_grid() {
+        let segments = parse_input(&read_input(5, true).unwrap()).unwrap();
+        let sweep = Sweep::new(&segments, SegmentFilter::Orthogonal);
+        assert_eq!(sweep.overlaps(), 5);
+        let sweep = Sweep::new(&segments, SegmentFilter::Diagonal);
+        assert_eq!(sweep.overlaps(), 12);
+
+        // Pseudo-random segments at any angle, crossing each other a lot
+        let mut seed = 7u32;
+        let mut next = |bound: u32| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            ((seed >> 16) % bound) as i32
+        };
+        let mut segments = vec![];
+        for _ in 0..300 {
+            let (x, y, length) = (next(40) - 20, next(40) - 20, next(20));
+            let (dx, dy) =
+                [(1, 0), (0, 1), (1, 1), (1, -1), (-1, 0), (2, 1), (1, -3)][next(7) as usize];
+            let end = Point {
+                x: x + dx * length,
+                y: y + dy * length,
+            };
+            segments.push(LineSegment::new(Point { x, y }, end));
+        }
+        for filter in [
+            SegmentFilter::Orthogonal,
+            SegmentFilter::Diagonal,
+            SegmentFilter::Any,
+        ] {
+            let grid = build_grid(&segments, filter);
+            let sweep = Sweep::new(&segments, filter);
+            assert_eq!(sweep.overlaps(), grid.overlaps());
+            assert_eq!(sweep.histogram(), grid.histogram());
+        }
+    }
+
+    #[test]
+    fn thresholds_around_the_origin() {
+        let segments =
+            parse_input("-2,-2 -> 2,2\n-2,2 -> 2,-2\n-2,0 -> 2,0\n-1,-2 -> 1,2\n").unwrap();
+        let grid = build_grid(&segments, SegmentFilter::Diagonal);
+        let sweep = Sweep::new(&segments, SegmentFilter::Diagonal);
+        let histogram = BTreeMap::from([(1, 12), (3, 1)]);
+        assert_eq!(grid.histogram(), histogram);
+        assert_eq!(sweep.histogram(), histogram);
+        assert_eq!(sweep.overlaps_at_least(3), 1);
+        assert_eq!(sweep.overlaps_at_least(4), 0);
+        // The steep segment also goes through the origin
+        let grid = build_grid(&segments, SegmentFilter::Any);
+        let sweep = Sweep::new(&segments, SegmentFilter::Any);
+        assert_eq!(grid.overlaps_at_least(4), 1);
+        assert_eq!(sweep.overlaps_at_least(4), 1);
+        assert_eq!(sweep.overlaps_at_least(1), grid.overlaps_at_least(1));
+    }
+
+    #[test]
+    fn images() {
+        let segments = parse_input("-1,0 -> 1,0\n0,-1 -> 0,1\n").unwrap();
+        let grid = build_grid(&segments, SegmentFilter::Orthogonal);
+        let mut pgm = vec![];
+        grid.write_pgm(&mut pgm).unwrap();
+        assert_eq!(
+            &pgm[pgm.len() - 9..],
+            &[0, 127, 0, 127, 255, 127, 0, 127, 0]
+        );
+        let mut svg = vec![];
+        grid.write_svg(&mut svg, 2).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains("viewBox=\"-1 -1 3 3\""));
+        assert_eq!(svg.matches("<line").count(), 2);
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"red\"/>"));
+    }
+
+    #[test]
+    fn sweep_large_coordinates() {
+        let segments = parse_input(
+            "0,0 -> 3000000,3000000\n3000000,0 -> 0,3000000\n0,7 -> 2000000,7\n1000000,7 -> 4000000,7\n",
+        )
+        .unwrap();
+        // The horizontal segments share 1000001 points, the diagonals cross each other and
+        // both cross the horizontal segments
+        let sweep = Sweep::new(&segments, SegmentFilter::Diagonal);
+        assert_eq!(sweep.overlaps(), 1000001 + 1 + 2);
+        // The parts go through the sweep too
+        assert_eq!(Day5::part_one(&segments).unwrap().answer, 1000001);
+        assert_eq!(Day5::part_two(&segments).unwrap().answer, 1000001 + 1 + 2);
+    }
+}
@@ -0,0 +1,466 @@
+use crate::{
+    info, input_lines, trace, AdventError, AdventResult, Detailed, Part, Solution, Variant,
+};
+
+/// Reads the fishes timers, comma separated and from 0 to 8.
+fn parse_input(input: &str) -> AdventResult<Vec<u8>> {
+    let mut fishes = vec![];
+    for line in input_lines(Day6::DAY, input) {
+        let clean_buffer = line.text.trim();
+        if clean_buffer.is_empty() {
+            continue;
+        }
+        for n in clean_buffer.split(',') {
+            match line.parse::<u8>(n, "a timer from 0 to 8")? {
+                timer @ 0..=8 => fishes.push(timer),
+                _ => return Err(line.error(n, "a timer from 0 to 8")),
+            }
+        }
+    }
+    Ok(fishes)
+}
+
+/// How the lanternfishes reproduce and die, the puzzle by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanternfishModel {
+    /// Days between two births of a fish.
+    pub cycle: usize,
+    /// Days a newborn waits on top of the cycle before its first birth.
+    pub newborn_extra_delay: usize,
+    /// Days a fish lives, the initial ones being born on day 0. `None` when they never die.
+    pub max_age: Option<usize>,
+}
+
+impl Default for LanternfishModel {
+    fn default() -> Self {
+        Self {
+            cycle: 7,
+            newborn_extra_delay: 2,
+            max_age: None,
+        }
+    }
+}
+
+impl LanternfishModel {
+    /// The stage totals of the fishes, one stage per timer value, newborns at the last one.
+    fn stages(&self, fishes: &[u8]) -> AdventResult<Vec<u64>> {
+        if self.cycle == 0 || self.max_age == Some(0) {
+            return Err(AdventError::InvalidArgument(format!(
+                "{:?} needs a cycle and a max age of at least one day",
+                self
+            )));
+        }
+        let mut fish_states = vec![0_u64; self.cycle + self.newborn_extra_delay];
+        for &timer in fishes {
+            *fish_states.get_mut(timer as usize).ok_or_else(|| {
+                AdventError::InvalidArgument(format!("timer {} is too large for {:?}", timer, self))
+            })? += 1;
+        }
+        Ok(fish_states)
+    }
+
+    /// The timer of a fish `days` after it was `timer`.
+    fn timer_after(&self, timer: usize, days: usize) -> usize {
+        if days <= timer {
+            timer - days
+        } else {
+            self.cycle - 1 - (days - timer - 1) % self.cycle
+        }
+    }
+}
+
+fn overflow(day: usize) -> AdventError {
+    AdventError::Overflow(format!("the population on day {}", day))
+}
+
+/// The population of every day from 0 to `days`, following each fish.
+pub fn growth_lazy(fishes: &[u8], days: usize, model: &LanternfishModel) -> AdventResult<Vec<u64>> {
+    let newborn = model.stages(fishes)?.len() - 1;
+    // The timer and age of each fish
+    let mut fish_pool: Vec<(usize, usize)> = fishes.iter().map(|&t| (t as usize, 0)).collect();
+    let mut series = vec![fish_pool.len() as u64];
+
+    trace!("  Initial state: {:?}", &fishes);
+    for i in 0..days {
+        for j in 0..fish_pool.len() {
+            let (timer, age) = &mut fish_pool[j];
+            *age += 1;
+            if *timer == 0 {
+                *timer = model.cycle - 1;
+                fish_pool.push((newborn, 0));
+            } else {
+                *timer -= 1;
+            }
+        }
+        if let Some(max_age) = model.max_age {
+            fish_pool.retain(|&(_, age)| age < max_age);
+        }
+        trace!(
+            "After {:2} day(s): {:?}",
+            i + 1,
+            fish_pool
+                .iter()
+                .map(|&(timer, _)| timer)
+                .collect::<Vec<_>>()
+        );
+        series.push(fish_pool.len() as u64);
+    }
+    Ok(series)
+}
+
+/// Rotation based stage totals, avoids the lazy method that over-allocates the vector.
+///
+/// Returns the population of every day from 0 to `days`, fails when it overflows.
+pub fn growth(fishes: &[u8], days: usize, model: &LanternfishModel) -> AdventResult<Vec<u64>> {
+    let mut fish_states = model.stages(fishes)?;
+    let initial = fish_states.clone();
+    let newborn = fish_states.len() - 1;
+    // Fishes born each day, to know when they die
+    let mut births = vec![0_u64];
+    let mut series = vec![fishes.len() as u64];
+    info!("  Initial state: {:?}", &fishes);
+    // Diagram for sample fishes ages 3, 4, 3, 1, 2
+    // Age stages   0  1  2  3  4  5  6  7  8
+    // Day 1 Stages 1, 1, 2, 1, 0, 0, 0, 0, 0 Fishes ages: 2, 3, 2, 0, 1
+    // Day 2 Stages 1, 2, 1, 0, 0, 0, 1, 0, 1 Fishes ages: 1, 2, 1, 6, 0, 8
+    // We rotate left, moving all fishes from stage 0 to 8 and as so we need to also add them to the stage 6 fishes
+    // that were 0. The rotation simulates the days passing.
+    for day in 1..=days {
+        fish_states.rotate_left(1);
+        // The parents are as many as the newborns, on the same stage without extra delay
+        let born = fish_states[newborn];
+        fish_states[model.cycle - 1] = fish_states[model.cycle - 1]
+            .checked_add(born)
+            .ok_or_else(|| overflow(day))?;
+        births.push(born);
+        // The fishes born `max_age` days ago all die today, they share the same timer
+        match model.max_age {
+            Some(max_age) if day == max_age => {
+                for (timer, &count) in initial.iter().enumerate() {
+                    fish_states[model.timer_after(timer, max_age)] -= count;
+                }
+            }
+            Some(max_age) if day > max_age => {
+                fish_states[model.timer_after(newborn, max_age)] -= births[day - max_age];
+            }
+            _ => {}
+        }
+        info!("After {:2} day(s): {:?}", day, &fish_states);
+        let total = fish_states
+            .iter()
+            .try_fold(0_u64, |total, &count| total.checked_add(count));
+        series.push(total.ok_or_else(|| overflow(day))?);
+    }
+    Ok(series)
+}
+
+/// The numbers [`growth_matrix`] counts the fishes with.
+pub trait Count: Copy {
+    fn from_u64(n: u64) -> Self;
+    fn is_zero(self) -> bool;
+    /// `None` on overflow.
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// `None` on overflow.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! checked_count {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                fn from_u64(n: u64) -> Self {
+                    n as $t
+                }
+
+                fn is_zero(self) -> bool {
+                    self == 0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+checked_count!(u64, u128);
+
+/// A count modulo `M`, which never overflows. `Modulo<0>` does not compile once used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modulo<const M: u64>(pub u64);
+
+impl<const M: u64> Modulo<M> {
+    const NOT_ZERO: () = assert!(M > 0, "a count modulo 0 is a division by zero");
+}
+
+impl<const M: u64> Count for Modulo<M> {
+    fn from_u64(n: u64) -> Self {
+        let () = Self::NOT_ZERO;
+        Self(n % M)
+    }
+
+    fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let () = Self::NOT_ZERO;
+        Some(Self(
+            ((self.0 as u128 + other.0 as u128) % M as u128) as u64,
+        ))
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        let () = Self::NOT_ZERO;
+        Some(Self((self.0 as u128 * other.0 as u128 % M as u128) as u64))
+    }
+}
+
+/// The product of two counts, `None` when it overflows. An overflowed count times 0 is 0.
+fn product<C: Count>(a: Option<C>, b: Option<C>) -> Option<C> {
+    match (a, b) {
+        (Some(a), _) if a.is_zero() => Some(a),
+        (_, Some(b)) if b.is_zero() => Some(b),
+        (Some(a), Some(b)) => a.checked_mul(b),
+        _ => None,
+    }
+}
+
+fn sum<C: Count>(a: Option<C>, b: Option<C>) -> Option<C> {
+    a?.checked_add(b?)
+}
+
+/// Square matrix of counts, `None` where a count overflowed.
+///
+/// Every count is positive, so a sum or product involving an overflowed count overflows too,
+/// unless multiplied by 0.
+#[derive(Debug, Clone)]
+struct Matrix<C> {
+    size: usize,
+    cells: Vec<Option<C>>,
+}
+
+impl<C: Count> Matrix<C> {
+    fn from_fn(size: usize, cell: impl Fn(usize, usize) -> u64) -> Self {
+        let cells = (0..size * size)
+            .map(|index| Some(C::from_u64(cell(index / size, index % size))))
+            .collect();
+        Self { size, cells }
+    }
+
+    fn get(&self, row: usize, column: usize) -> Option<C> {
+        self.cells[row * self.size + column]
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let size = self.size;
+        let cells = (0..size * size)
+            .map(|index| {
+                let (row, column) = (index / size, index % size);
+                (0..size)
+                    .map(|k| product(self.get(row, k), other.get(k, column)))
+                    .fold(Some(C::from_u64(0)), sum)
+            })
+            .collect();
+        Self { size, cells }
+    }
+
+    fn pow(&self, mut exponent: u64) -> Self {
+        let mut result = Self::from_fn(self.size, |row, column| (row == column) as u64);
+        let mut power = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&power);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                power = power.mul(&power);
+            }
+        }
+        result
+    }
+}
+
+/// The population after `days`, in O(log days) with the powers of the daily transition of the
+/// stage totals. Fails on timers past 8 or when the count overflows.
+pub fn growth_matrix<C: Count>(fishes: &[u8], days: u64) -> AdventResult<C> {
+    let mut fish_states = [0_u64; 9];
+    for &timer in fishes {
+        *fish_states
+            .get_mut(timer as usize)
+            .ok_or_else(|| AdventError::InvalidArgument(format!("timer {} is past 8", timer)))? +=
+            1;
+    }
+    // The stage totals of a day come from the previous day ones, as in `growth`
+    let transition = Matrix::<C>::from_fn(9, |to, from| match (to, from) {
+        (6 | 8, 0) => 1,
+        (to, from) => (from == to + 1) as u64,
+    });
+    let after = transition.pow(days);
+    (0..9)
+        .flat_map(|to| (0..9).map(move |from| (to, from)))
+        .map(|(to, from)| product(after.get(to, from), Some(C::from_u64(fish_states[from]))))
+        .fold(Some(C::from_u64(0)), sum)
+        .ok_or_else(|| AdventError::Overflow(format!("the population after {} days", days)))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Vec<u8>;
+    type Answer1 = Detailed<u64>;
+    type Answer2 = Detailed<u64>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(lantern_fish: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let series = growth(lantern_fish, 80, &LanternfishModel::default())?;
+        Ok(Detailed::new(series[80]).with("initial_fishes", lantern_fish.len()))
+    }
+
+    fn part_two(lantern_fish: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let series = growth(lantern_fish, 256, &LanternfishModel::default())?;
+        Ok(Detailed::new(series[256])
+            .with("initial_fishes", lantern_fish.len())
+            .with("after_80_days", series[80]))
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        fn matrix(lantern_fish: &[u8], days: u64) -> AdventResult<String> {
+            Ok(growth_matrix::<u64>(lantern_fish, days)?.to_string())
+        }
+        vec![
+            Variant {
+                name: "growth_lazy",
+                part: Part::One,
+                solve: |lantern_fish| {
+                    let series = growth_lazy(lantern_fish, 80, &LanternfishModel::default())?;
+                    Ok(series[80].to_string())
+                },
+            },
+            Variant {
+                name: "matrix",
+                part: Part::One,
+                solve: |lantern_fish| matrix(lantern_fish, 80),
+            },
+            Variant {
+                name: "matrix",
+                part: Part::Two,
+                solve: |lantern_fish| matrix(lantern_fish, 256),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn lazy_growth() {
+        let input = read_input(6, true).expect("Error reading input");
+        let lantern_fish = parse_input(&input).expect("Invalid data");
+        let series = growth_lazy(&lantern_fish, 80, &LanternfishModel::default()).unwrap();
+        assert_eq!(series[..6], [5, 5, 6, 7, 9, 10]);
+        assert_eq!(series[18], 26);
+        assert_eq!(series[80], 5934);
+    }
+
+    #[test]
+    fn models() {
+        let input = read_input(6, true).expect("Error reading input");
+        let lantern_fish = parse_input(&input).expect("Invalid data");
+        for (cycle, newborn_extra_delay, max_age) in [
+            (7, 2, None),
+            (7, 2, Some(10)),
+            (3, 2, None),
+            (5, 0, Some(4)),
+            (9, 4, Some(9)),
+            (1, 8, Some(1)),
+        ] {
+            let model = LanternfishModel {
+                cycle,
+                newborn_extra_delay,
+                max_age,
+            };
+            assert_eq!(
+                growth(&lantern_fish, 60, &model).unwrap(),
+                growth_lazy(&lantern_fish, 60, &model).unwrap(),
+                "{:?}",
+                model
+            );
+        }
+        // A fish living two days gives birth every other day, dying once its child is born
+        let model = LanternfishModel {
+            cycle: 2,
+            newborn_extra_delay: 0,
+            max_age: Some(2),
+        };
+        assert_eq!(growth(&[0], 5, &model).unwrap(), vec![1, 2, 1, 1, 1, 1]);
+        let model = LanternfishModel { cycle: 0, ..model };
+        assert!(growth(&[0], 5, &model).is_err());
+        let model = LanternfishModel {
+            cycle: 3,
+            newborn_extra_delay: 1,
+            max_age: None,
+        };
+        assert!(growth_lazy(&[4], 5, &model).is_err());
+    }
+
+    #[test]
+    fn matrix_growth() {
+        let input = read_input(6, true).expect("Error reading input");
+        let lantern_fish = parse_input(&input).expect("Invalid data");
+        let model = LanternfishModel::default();
+        let series = growth(&lantern_fish, 300, &model).unwrap();
+        for (days, &count) in series.iter().enumerate() {
+            assert_eq!(
+                growth_matrix::<u64>(&lantern_fish, days as u64).unwrap(),
+                count
+            );
+        }
+        assert_eq!(
+            growth_matrix::<u64>(&lantern_fish, 256).unwrap(),
+            26984457539
+        );
+        // Both overflow u64 on the same day, u128 goes further
+        let overflow = (300..)
+            .find(|&days| growth_matrix::<u64>(&lantern_fish, days).is_err())
+            .unwrap();
+        assert!(matches!(
+            growth_matrix::<u64>(&lantern_fish, overflow),
+            Err(AdventError::Overflow(_))
+        ));
+        assert!(growth(&lantern_fish, overflow as usize - 1, &model).is_ok());
+        assert!(matches!(
+            growth(&lantern_fish, overflow as usize, &model),
+            Err(AdventError::Overflow(_))
+        ));
+        let large = growth_matrix::<u128>(&lantern_fish, 600).unwrap();
+        assert!(large > u64::MAX as u128);
+        assert!(matches!(
+            growth_matrix::<u128>(&lantern_fish, 1000),
+            Err(AdventError::Overflow(_))
+        ));
+        // Timers past 8 are bad input, not an overflow
+        assert!(matches!(
+            growth_matrix::<u64>(&[3, 9], 10),
+            Err(AdventError::InvalidArgument(_))
+        ));
+        // Modulo a prime
+        const P: u64 = 1_000_000_007;
+        let modulo = growth_matrix::<Modulo<P>>(&lantern_fish, 600).unwrap();
+        assert_eq!(modulo.0 as u128, large % P as u128);
+        assert!(growth_matrix::<Modulo<P>>(&lantern_fish, 1_000_000).is_ok());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{input_lines, AdventError, AdventResult, Detailed, Part, Solution, Variant};
+
+fn parse_input(input: &str) -> AdventResult<Vec<u64>> {
+    let mut result = Vec::new();
+    for line in input_lines(Day7::DAY, input) {
+        let clean_buffer = line.text.trim();
+        if clean_buffer.is_empty() {
+            continue;
+        }
+        for n in clean_buffer.split(',') {
+            result.push(line.parse::<u64>(n, "a position")?);
+        }
+    }
+    Ok(result)
+}
+
+/// Brute force approach
+fn calculate_fuel_part1_lazy(positions: &[u64]) -> AdventResult<(u64, u64)> {
+    let min = *positions.iter().min().ok_or(AdventError::InvalidData)?;
+    let max = *positions.iter().max().ok_or(AdventError::InvalidData)?;
+    let mut fuel_cost = u64::MAX;
+    let mut best_position = 0;
+    for pos in min..=max {
+        let fuel: u64 = positions.iter().map(|&v| v.abs_diff(pos)).sum();
+        if fuel < fuel_cost {
+            fuel_cost = fuel;
+            best_position = pos;
+        }
+    }
+    Ok((fuel_cost, best_position))
+}
+
+/// A much smarter approach is to use the median as the best position.
+fn calculate_fuel_part1_smart(positions: &[u64]) -> AdventResult<(u64, u64)> {
+    let mut positions = positions.to_vec();
+    positions.sort_unstable();
+    let median = *positions
+        .get(positions.len() / 2)
+        .ok_or(AdventError::InvalidData)?;
+    let best_position = median;
+    let fuel_cost = positions.iter().map(|&v| v.abs_diff(best_position)).sum();
+    Ok((fuel_cost, best_position))
+}
+
+/// Brute force approach
+fn calculate_fuel_part2(positions: &[u64]) -> AdventResult<(u64, u64)> {
+    let min = *positions.iter().min().ok_or(AdventError::InvalidData)?;
+    let max = *positions.iter().max().ok_or(AdventError::InvalidData)?;
+    let mut fuel_cost = u64::MAX;
+    let mut best_position = 0;
+    for pos in min..=max {
+        let fuel: u64 = positions
+            .iter()
+            .map(|&v| {
+                let n = v.abs_diff(pos);
+                n * (n + 1) / 2
+            })
+            .sum();
+        if fuel < fuel_cost {
+            fuel_cost = fuel;
+            best_position = pos;
+        }
+    }
+    Ok((fuel_cost, best_position))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Vec<u64>;
+    type Answer1 = Detailed<u64>;
+    type Answer2 = Detailed<u64>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(positions: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let (fuel_cost, best_position) = calculate_fuel_part1_smart(positions)?;
+        Ok(Detailed::new(fuel_cost).with("best_position", best_position))
+    }
+
+    fn part_two(positions: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let (fuel_cost, best_position) = calculate_fuel_part2(positions)?;
+        Ok(Detailed::new(fuel_cost).with("best_position", best_position))
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![Variant {
+            name: "lazy",
+            part: Part::One,
+            solve: |positions| Ok(calculate_fuel_part1_lazy(positions)?.0.to_string()),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn validate_fuel_part1_lazy() {
+        let input = read_input(7, true).expect("Invalid data");
+        let positions = parse_input(&input).expect("Invalid data");
+        let result = calculate_fuel_part1_lazy(&positions).expect("Invalid data");
+        assert_eq!(result, (37, 2));
+        assert!(matches!(
+            calculate_fuel_part1_lazy(&[]),
+            Err(AdventError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn validate_fuel_part1_smart() {
+        let input = read_input(7, true).expect("Invalid data");
+        let positions = parse_input(&input).expect("Invalid data");
+        let result = calculate_fuel_part1_smart(&positions).expect("Invalid data");
+        assert_eq!(result, (37, 2));
+        assert!(matches!(
+            calculate_fuel_part1_smart(&[]),
+            Err(AdventError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn validate_fuel_part2() {
+        let input = read_input(7, true).expect("Invalid data");
+        let positions = parse_input(&input).expect("Invalid data");
+        let result = calculate_fuel_part2(&positions).expect("Invalid data");
+        assert_eq!(result, (168, 5));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{input_lines, AdventError, AdventResult, Detailed, Solution};
+
+/// Just for reference
+#[allow(dead_code)]
+const NUMBERS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+#[derive(Debug, Default)]
+pub struct SegmentDisplay {
+    input: Vec<Vec<u8>>,
+    output: Vec<Vec<u8>>,
+}
+
+impl SegmentDisplay {
+    fn new(input_numbers: Vec<Vec<u8>>, output_numbers: Vec<Vec<u8>>) -> Self {
+        Self {
+            input: input_numbers,
+            output: output_numbers,
+        }
+    }
+
+    /// The four output digits as a number.
+    fn value(&self) -> u64 {
+        self.output
+            .iter()
+            .fold(0, |result, digit| result * 10 + self.digit(digit) as u64)
+    }
+
+    fn digit_1(&self) -> Vec<u8> {
+        self.input
+            .iter()
+            .find(|x| x.len() == 2)
+            .map_or(vec![], |x| x.clone())
+    }
+
+    fn digit_4(&self) -> Vec<u8> {
+        self.input
+            .iter()
+            .find(|x| x.len() == 4)
+            .map_or(vec![], |x| x.clone())
+    }
+
+    /// The digit of a pattern, which the parser checked has from 2 to 7 segments.
+    fn digit(&self, digit: &[u8]) -> u32 {
+        match digit.len() {
+            2 => 1,
+            3 => 7,
+            4 => 4,
+            5 => {
+                // Can be 2, 3 or 5
+                if digit_includes(digit, &self.digit_1()) {
+                    3
+                } else {
+                    let digit_bd = digit_xor(&self.digit_4(), &self.digit_1());
+                    if digit_includes(digit, &digit_bd) {
+                        5
+                    } else {
+                        2
+                    }
+                }
+            }
+            6 => {
+                // Can be 0, 6 or 9
+                if digit_includes(digit, &self.digit_1()) {
+                    if digit_includes(digit, &self.digit_4()) {
+                        9
+                    } else {
+                        0
+                    }
+                } else {
+                    6
+                }
+            }
+            _ => 8,
+        }
+    }
+}
+
+fn digit_includes(digit: &[u8], base: &[u8]) -> bool {
+    base.iter().all(|&x| digit.contains(&x))
+}
+
+fn digit_xor(digit: &[u8], base: &[u8]) -> Vec<u8> {
+    digit
+        .iter()
+        .filter(|&x| !base.contains(x))
+        .cloned()
+        .collect()
+}
+
+fn parse_input(input: &str) -> AdventResult<Vec<SegmentDisplay>> {
+    let mut result = vec![];
+    for line in input_lines(Day8::DAY, input) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let (input_part, output_part) = line
+            .text
+            .split_once('|')
+            .ok_or_else(|| line.error_at_end("'|'"))?;
+        // Exactly `count` patterns, an extra one is reported as `after`
+        let parse_digits = |part: &str, count: usize, name: &str, after: &str| {
+            let mut digits = vec![];
+            for n in part.split_whitespace() {
+                if digits.len() == count {
+                    return Err(line.error(n, after));
+                }
+                if !(2..=7).contains(&n.len()) || !n.bytes().all(|b| (b'a'..=b'g').contains(&b)) {
+                    return Err(line.error(n, "2 to 7 segments from a to g"));
+                }
+                digits.push(n.bytes().collect());
+            }
+            if digits.len() < count {
+                let end = part.trim_end().len();
+                return Err(line.error(&part[end..end], format!("{} {}", count, name)));
+            }
+            Ok::<Vec<Vec<u8>>, AdventError>(digits)
+        };
+        let input_numbers = parse_digits(input_part, 10, "patterns", "'|'")?;
+        let output_numbers = parse_digits(output_part, 4, "output digits", "the end of the line")?;
+        for size in [2, 4] {
+            if !input_numbers.iter().any(|n| n.len() == size) {
+                return Err(
+                    line.error(input_part.trim(), format!("a pattern of {} segments", size))
+                );
+            }
+        }
+        result.push(SegmentDisplay::new(input_numbers, output_numbers));
+    }
+    Ok(result)
+}
+
+fn solve_part1(segment_display: &[SegmentDisplay]) -> AdventResult<usize> {
+    const LENS: [usize; 4] = [2, 4, 3, 7];
+    let mut count = 0;
+    for segment_number in segment_display.iter() {
+        count += segment_number
+            .output
+            .iter()
+            .filter(|x| LENS.contains(&x.len()))
+            .count();
+    }
+    Ok(count)
+}
+
+fn solve_part2(segment_display: &[SegmentDisplay]) -> u64 {
+    segment_display.iter().map(|s| s.value()).sum()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Vec<SegmentDisplay>;
+    type Answer1 = Detailed<usize>;
+    type Answer2 = Detailed<u64>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_one(numbers: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        Ok(Detailed::new(solve_part1(numbers)?).with("displays", numbers.len()))
+    }
+
+    fn part_two(numbers: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        Ok(Detailed::new(solve_part2(numbers)).with("displays", numbers.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_column(input: &str) -> (usize, String) {
+        match parse_input(input).unwrap_err() {
+            AdventError::Parse { column, found, .. } => (column, found),
+            error => panic!("{}", error),
+        }
+    }
+
+    #[test]
+    fn malformed_displays() {
+        let patterns = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb";
+        let display = parse_input(&format!("{} | fdgacbe cefdb cefbgd gcbe\n", patterns)).unwrap();
+        assert_eq!(display[0].value(), 8394);
+        assert_eq!(error_column("ab cd |\n"), (6, String::new()));
+        let too_many = format!("{} | fdgacbe cefdb cefbgd gcbe be\n", patterns);
+        assert_eq!(
+            error_column(&too_many),
+            (too_many.rfind("be").unwrap() + 1, "be".to_owned())
+        );
+        let no_four = patterns.replace("cgeb", "cgebf");
+        let no_four = format!("{} | fdgacbe cefdb cefbgd gcbe\n", no_four);
+        assert_eq!(error_column(&no_four).0, 1);
+        let unknown = format!("{} | fdgacbe cefdb cefbgd g\n", patterns);
+        assert_eq!(error_column(&unknown), (unknown.len() - 1, "g".to_owned()));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{grid::Grid2D, AdventResult, Detailed, Solution};
+
+/// Flood fills the basin from the position, tagging the visited heights as 9, the basin
+/// edges. Returns its lowest height and size.
+fn simple_kernel(grid: &mut Grid2D<u8>, x: usize, y: usize) -> Option<(u8, usize)> {
+    let mut lowest = *grid.get(x, y).filter(|&&height| height < 9)?;
+    // Default basin size
+    let mut size = 1;
+    // Since we visited this tag it
+    grid[(x, y)] = 9;
+    // Check the cross elements
+    for (nx, ny) in grid.neighbours4(x, y) {
+        if let Some((v, s)) = simple_kernel(grid, nx, ny) {
+            size += s;
+            lowest = lowest.min(v);
+        }
+    }
+    Some((lowest, size))
+}
+
+fn basins(grid: &Grid2D<u8>) -> Vec<(u8, usize)> {
+    let mut grid = grid.clone();
+    let mut basins_sizes: Vec<_> = grid
+        .positions()
+        .filter_map(|(x, y)| simple_kernel(&mut grid, x, y))
+        .collect();
+    basins_sizes.sort_unstable_by_key(|k| k.1);
+    basins_sizes
+}
+
+/// Each basin has a single low point.
+fn risk_level(basins: &[(u8, usize)]) -> u32 {
+    basins.iter().map(|(v, _)| (v + 1) as u32).sum()
+}
+
+/// The sizes of the three largest basins, largest first.
+fn largest_basins(basins: &[(u8, usize)]) -> Vec<usize> {
+    basins.iter().rev().take(3).map(|&(_, size)| size).collect()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Grid2D<u8>;
+    type Answer1 = Detailed<u32>;
+    type Answer2 = Detailed<usize>;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>> {
+        Grid2D::from_digits(Self::DAY, input)
+    }
+
+    fn part_one(grid: &Self::Input<'_>) -> AdventResult<Self::Answer1> {
+        let basins = basins(grid);
+        Ok(Detailed::new(risk_level(&basins)).with("low_points", basins.len()))
+    }
+
+    fn part_two(grid: &Self::Input<'_>) -> AdventResult<Self::Answer2> {
+        let largest = largest_basins(&basins(grid));
+        Ok(Detailed::new(largest.iter().product()).with("largest_basins", largest))
+    }
+}
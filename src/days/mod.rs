@@ -0,0 +1,88 @@
+//! Every day solution, see [`Solution`](crate::Solution).
+
+use crate::{
+    bench::{bench_solution, BenchConfig, BenchResult},
+    cli::Options,
+    run, AdventError, AdventResult, Answers, DayReport, Solution,
+};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Days with a solution, in order.
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=14;
+
+/// Something to do with a day solution, it goes from the day number to the solution type.
+pub trait DayVisitor {
+    type Output;
+
+    fn visit<S: Solution>(self) -> AdventResult<Self::Output>;
+}
+
+pub fn visit<V: DayVisitor>(day: u8, visitor: V) -> AdventResult<V::Output> {
+    match day {
+        1 => visitor.visit::<day1::Day1>(),
+        2 => visitor.visit::<day2::Day2>(),
+        3 => visitor.visit::<day3::Day3>(),
+        4 => visitor.visit::<day4::Day4>(),
+        5 => visitor.visit::<day5::Day5>(),
+        6 => visitor.visit::<day6::Day6>(),
+        7 => visitor.visit::<day7::Day7>(),
+        8 => visitor.visit::<day8::Day8>(),
+        9 => visitor.visit::<day9::Day9>(),
+        10 => visitor.visit::<day10::Day10>(),
+        11 => visitor.visit::<day11::Day11>(),
+        12 => visitor.visit::<day12::Day12>(),
+        13 => visitor.visit::<day13::Day13>(),
+        14 => visitor.visit::<day14::Day14>(),
+        _ => Err(AdventError::UnknownDay(day)),
+    }
+}
+
+struct Run<'a> {
+    options: &'a Options,
+    answers: &'a Answers,
+}
+
+impl DayVisitor for Run<'_> {
+    type Output = DayReport;
+
+    fn visit<S: Solution>(self) -> AdventResult<DayReport> {
+        run::<S>(self.options, self.answers)
+    }
+}
+
+/// Runs the day solution with the given options.
+pub fn run_day(day: u8, options: &Options, answers: &Answers) -> AdventResult<DayReport> {
+    visit(day, Run { options, answers })
+}
+
+struct Bench<'a> {
+    input: &'a str,
+    config: BenchConfig,
+}
+
+impl DayVisitor for Bench<'_> {
+    type Output = Vec<BenchResult>;
+
+    fn visit<S: Solution>(self) -> AdventResult<Vec<BenchResult>> {
+        bench_solution::<S>(self.input, self.config)
+    }
+}
+
+/// Benchmarks the day solution and its variants on the given input.
+pub fn bench_day(day: u8, input: &str, config: BenchConfig) -> AdventResult<Vec<BenchResult>> {
+    visit(day, Bench { input, config })
+}
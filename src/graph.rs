@@ -0,0 +1,225 @@
+//! Graphs whose nodes are interned to integer ids, and the enumeration of their paths.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Index of a node in its graph.
+pub type NodeId = usize;
+
+/// A graph of `N` named nodes, directed or not.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    directed: bool,
+    names: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<NodeId>>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            names: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    /// Id of the node, which is added when first seen.
+    pub fn intern(&mut self, name: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// Adds the edge, and the one back when the graph is undirected.
+    pub fn add_edge(&mut self, from: N, to: N) -> (NodeId, NodeId) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.edges[from].push(to);
+        if !self.directed {
+            self.edges[to].push(from);
+        }
+        (from, to)
+    }
+
+    pub fn id(&self, name: &N) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn name(&self, id: NodeId) -> &N {
+        &self.names[id]
+    }
+
+    /// Every node id, in the order they were added.
+    pub fn nodes(&self) -> std::ops::Range<NodeId> {
+        0..self.names.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id]
+    }
+
+    /// Calls `found` with every path from `from` to `to` the policy allows, a path stops the
+    /// first time it reaches `to`.
+    pub fn paths(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        policy: &impl VisitPolicy,
+        mut found: impl FnMut(&[NodeId]),
+    ) {
+        let mut walk = Walk {
+            path: vec![from],
+            visits: vec![0; self.len()],
+        };
+        walk.visits[from] = 1;
+        self.walk(&mut walk, to, policy, &mut found);
+    }
+
+    pub fn count_paths(&self, from: NodeId, to: NodeId, policy: &impl VisitPolicy) -> usize {
+        let mut count = 0;
+        self.paths(from, to, policy, |_| count += 1);
+        count
+    }
+
+    fn walk(
+        &self,
+        walk: &mut Walk,
+        to: NodeId,
+        policy: &impl VisitPolicy,
+        found: &mut impl FnMut(&[NodeId]),
+    ) {
+        let current = *walk.path.last().unwrap();
+        for &next in self.neighbours(current) {
+            if !policy.may_visit(walk, next) {
+                continue;
+            }
+            walk.path.push(next);
+            walk.visits[next] += 1;
+            if next == to {
+                found(&walk.path);
+            } else {
+                self.walk(walk, to, policy, found);
+            }
+            walk.visits[next] -= 1;
+            walk.path.pop();
+        }
+    }
+}
+
+/// A path being enumerated.
+#[derive(Debug)]
+pub struct Walk {
+    path: Vec<NodeId>,
+    visits: Vec<u32>,
+}
+
+impl Walk {
+    /// The nodes so far, starting with the first one.
+    pub fn path(&self) -> &[NodeId] {
+        &self.path
+    }
+
+    /// How many times the node is in the path.
+    pub fn visits(&self, id: NodeId) -> u32 {
+        self.visits[id]
+    }
+}
+
+/// Decides where the paths may go next.
+pub trait VisitPolicy {
+    fn may_visit(&self, walk: &Walk, next: NodeId) -> bool;
+}
+
+/// A custom policy.
+impl<F: Fn(&Walk, NodeId) -> bool> VisitPolicy for F {
+    fn may_visit(&self, walk: &Walk, next: NodeId) -> bool {
+        self(walk, next)
+    }
+}
+
+/// The limited nodes are visited at most once, the others any number of times.
+pub struct VisitOnce<L>(pub L);
+
+impl<L: Fn(NodeId) -> bool> VisitPolicy for VisitOnce<L> {
+    fn may_visit(&self, walk: &Walk, next: NodeId) -> bool {
+        !(self.0)(next) || walk.visits(next) == 0
+    }
+}
+
+/// Like [`VisitOnce`], but a single limited node may be visited twice. The first node of the
+/// path is never visited again.
+pub struct OneTwice<L>(pub L);
+
+impl<L: Fn(NodeId) -> bool> VisitPolicy for OneTwice<L> {
+    fn may_visit(&self, walk: &Walk, next: NodeId) -> bool {
+        let limited = &self.0;
+        if !limited(next) || walk.visits(next) == 0 {
+            return true;
+        }
+        next != walk.path[0]
+            && !walk
+                .path
+                .iter()
+                .any(|&id| limited(id) && walk.visits(id) > 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_nodes() {
+        let mut graph = Graph::undirected();
+        let (a, b) = graph.add_edge("a", "b");
+        assert_eq!(graph.intern("a"), a);
+        assert_eq!(graph.neighbours(b), &[a]);
+        assert_eq!(graph.name(b), &"b");
+        let mut graph = Graph::directed();
+        let (a, b) = graph.add_edge("a", "b");
+        assert_eq!(graph.neighbours(a), &[b]);
+        assert!(graph.neighbours(b).is_empty());
+        assert_eq!(graph.id(&"c"), None);
+    }
+
+    #[test]
+    fn paths_follow_the_policy() {
+        // a - b - d with a loop b - c - b through a node that can be revisited
+        let mut graph = Graph::undirected();
+        graph.add_edge("a", "b");
+        graph.add_edge("b", "C");
+        graph.add_edge("b", "d");
+        let (a, d) = (graph.id(&"a").unwrap(), graph.id(&"d").unwrap());
+        let small = |id| graph.name(id).chars().all(|c| c.is_lowercase());
+        let mut paths = vec![];
+        graph.paths(a, d, &VisitOnce(small), |path| paths.push(path.to_vec()));
+        assert_eq!(paths, vec![vec![0, 1, 3]]);
+        // The b - C - b loop can be taken once when b may be visited twice
+        assert_eq!(graph.count_paths(a, d, &OneTwice(small)), 2);
+        let never_twice = |walk: &Walk, next| walk.visits(next) == 0;
+        assert_eq!(graph.count_paths(a, d, &never_twice), 1);
+    }
+}
@@ -0,0 +1,251 @@
+//! Rectangular grids, stored row by row, the shape of many puzzles.
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{input_lines, AdventResult};
+
+/// The four orthogonal neighbours.
+const CROSS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+/// The orthogonal and diagonal neighbours.
+const AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A `width` by `height` grid, indexed by `(x, y)` with `(0, 0)` at the top left.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Grid2D<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid2D<T> {
+    /// Builds the grid from its cells row by row, there must be `width * height` of them.
+    pub fn new(width: usize, height: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), width * height, "grid size mismatch");
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+        Self::new(width, height, data)
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Reads a rectangular map of characters, `cell` turning each one into a cell or `None`
+    /// when it is not `expected`. Blank lines are skipped.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> AdventResult<Self> {
+        let mut data = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        for line in input_lines(day, input) {
+            let clean_buffer = line.text.trim();
+            if clean_buffer.is_empty() {
+                continue;
+            }
+            let mut count = 0;
+            for (index, c) in clean_buffer.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    line.error(&clean_buffer[index..index + c.len_utf8()], expected)
+                })?;
+                data.push(value);
+                count += 1;
+            }
+            if height == 0 {
+                width = count;
+            } else if count != width {
+                return Err(line.error(clean_buffer, format!("{} cells", width)));
+            }
+            height += 1;
+        }
+        Ok(Self::new(width, height, data))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The cell, `None` outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.data[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.data[y * self.width + x])
+    }
+
+    /// Positions of the orthogonal neighbours inside the grid.
+    ///
+    /// The iterator does not borrow the grid, so it can be changed while walking them.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &CROSS)
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours inside the grid.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &AROUND)
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width { &self.data[x..] } else { &[] };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+}
+
+impl Grid2D<u8> {
+    /// Reads a rectangular map of single digits.
+    pub fn from_digits(day: u8, input: &str) -> AdventResult<Self> {
+        Self::parse(day, input, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("position outside of the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("position outside of the grid")
+    }
+}
+
+/// One line per row, the cells side by side.
+impl<T: Display> Display for Grid2D<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdventError;
+
+    #[test]
+    fn digits_must_be_rectangular() {
+        let grid = Grid2D::from_digits(9, "123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 5);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        let error = Grid2D::from_digits(9, "123\n45\n").unwrap_err();
+        assert!(matches!(error, AdventError::Parse { line: 2, .. }));
+        let error = Grid2D::from_digits(9, "123\n4a6\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AdventError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = Grid2D::filled(3, 3, 0);
+        let corner: Vec<_> = grid.neighbours4(0, 0).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours8(0, 2).count(), 3);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid2D::parse(0, "#.\n..\n.#\n", "# or .", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(grid.rows().nth(2), Some(&[false, true][..]));
+        let column: Vec<_> = grid.column(1).copied().collect();
+        assert_eq!(column, vec![false, false, true]);
+        assert_eq!(grid.columns().count(), 2);
+        let filled: Vec<_> = grid.iter().filter(|(_, &c)| c).map(|(p, _)| p).collect();
+        assert_eq!(filled, vec![(0, 0), (1, 2)]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Image files written by hand, to look at grids too large to print.
+
+use std::io::{self, Write};
+
+use crate::grid::Grid2D;
+
+/// Greyscale PGM of the counts, from black for 0 to white for the highest one.
+pub fn write_pgm(mut out: impl Write, counts: &Grid2D<u32>) -> io::Result<()> {
+    let max = counts.iter().map(|(_, &c)| c).max().unwrap_or(0).max(1);
+    write!(out, "P5\n{} {}\n255\n", counts.width(), counts.height())?;
+    let pixels: Vec<u8> = counts
+        .iter()
+        .map(|(_, &c)| (c as u64 * 255 / max as u64) as u8)
+        .collect();
+    out.write_all(&pixels)
+}
+
+/// Colour PPM of the counts, black for 0 then from dark red to yellow for the highest one.
+pub fn write_ppm(mut out: impl Write, counts: &Grid2D<u32>) -> io::Result<()> {
+    let max = counts.iter().map(|(_, &c)| c).max().unwrap_or(0).max(1);
+    write!(out, "P6\n{} {}\n255\n", counts.width(), counts.height())?;
+    let mut pixels = Vec::with_capacity(counts.len() * 3);
+    for (_, &c) in counts.iter() {
+        pixels.extend_from_slice(&heat(c, max));
+    }
+    out.write_all(&pixels)
+}
+
+fn heat(count: u32, max: u32) -> [u8; 3] {
+    if count == 0 {
+        return [0, 0, 0];
+    }
+    // From 0 to 510 over the counts from 1 to max, red first then green
+    let level = if max == 1 {
+        510
+    } else {
+        (count - 1) as u64 * 510 / (max - 1) as u64
+    };
+    [
+        (128 + level.min(255) * 127 / 255) as u8,
+        level.saturating_sub(255) as u8,
+        0,
+    ]
+}
+
+/// A minimal SVG document, in grid units.
+#[derive(Debug, Clone)]
+pub struct Svg {
+    body: String,
+    view_box: (i64, i64, u64, u64),
+}
+
+impl Svg {
+    /// The view box starts at `(x, y)`, its cells are one unit wide.
+    pub fn new(x: i64, y: i64, width: u64, height: u64) -> Self {
+        Self {
+            body: String::new(),
+            view_box: (x, y, width, height),
+        }
+    }
+
+    pub fn rect(&mut self, x: i64, y: i64, width: u64, height: u64, fill: &str) {
+        self.body += &format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            x, y, width, height, fill
+        );
+    }
+
+    /// A line between the centres of two cells.
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64), stroke: &str) {
+        self.body += &format!(
+            "<line x1=\"{}.5\" y1=\"{}.5\" x2=\"{}.5\" y2=\"{}.5\" stroke=\"{}\"/>\n",
+            from.0, from.1, to.0, to.1, stroke
+        );
+    }
+
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        let (x, y, width, height) = self.view_box;
+        write!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" \
+             stroke-width=\"0.3\" stroke-linecap=\"round\">\n\
+             <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"black\"/>\n{}</svg>\n",
+            x, y, width, height, x, y, width, height, self.body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn netpbm_headers() {
+        let counts = Grid2D::new(3, 1, vec![0, 1, 2]);
+        let mut pgm = vec![];
+        write_pgm(&mut pgm, &counts).unwrap();
+        assert_eq!(pgm, b"P5\n3 1\n255\n\x00\x7f\xff");
+        let mut ppm = vec![];
+        write_ppm(&mut ppm, &counts).unwrap();
+        assert!(ppm.starts_with(b"P6\n3 1\n255\n"));
+        assert_eq!(&ppm[ppm.len() - 9..], &[0, 0, 0, 128, 0, 0, 255, 255, 0]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Where the puzzle inputs are read from.
+
+use std::{
+    env,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::{AdventError, AdventResult};
+
+/// Environment variable with the directory holding the `dayN.txt` inputs.
+pub const INPUTS_DIR_VAR: &str = "ADVENT_INPUTS";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `dayN.txt` or `dayN_sample.txt` in the inputs directory, see [`inputs_dir`].
+    #[default]
+    Directory,
+    /// The inputs compiled into the binary, requires the `embed_inputs` feature.
+    Embedded,
+    /// A single file, the sample flag is ignored.
+    Path(PathBuf),
+    /// Standard input, the sample flag is ignored.
+    Stdin,
+    /// Input already read, e.g. by a binary that also uses it, the sample flag is ignored.
+    Text(String),
+}
+
+impl InputSource {
+    /// Parses a command line value, `-` being the standard input.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::Path(PathBuf::from(arg))
+        }
+    }
+
+    /// Whether the source can provide the input of any day or only of a single one.
+    pub fn is_per_day(&self) -> bool {
+        matches!(self, InputSource::Directory | InputSource::Embedded)
+    }
+
+    pub fn read(&self, day: u8, use_sample: bool) -> AdventResult<String> {
+        match self {
+            InputSource::Directory => Ok(read_to_string(
+                inputs_dir().join(file_name(day, use_sample)),
+            )?),
+            InputSource::Embedded => embedded(day, use_sample)
+                .map(str::to_owned)
+                .ok_or(AdventError::MissingInput(day)),
+            InputSource::Path(path) => Ok(read_to_string(path)?),
+            InputSource::Stdin => Ok(std::io::read_to_string(std::io::stdin())?),
+            InputSource::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+fn file_name(day: u8, use_sample: bool) -> String {
+    if use_sample {
+        format!("day{}_sample.txt", day)
+    } else {
+        format!("day{}.txt", day)
+    }
+}
+
+/// The inputs directory, taken from [`INPUTS_DIR_VAR`] when set so it doesn't depend on the
+/// current directory, otherwise the `inputs` directory of this crate.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os(INPUTS_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))
+}
+
+#[cfg(feature = "embed_inputs")]
+macro_rules! embed {
+    ( $( $day:literal ),* ) => {
+        fn embedded(day: u8, use_sample: bool) -> Option<&'static str> {
+            match (day, use_sample) {
+                $(
+                    ($day, false) => Some(include_str!(concat!("../inputs/day", $day, ".txt"))),
+                    ($day, true) => Some(include_str!(concat!("../inputs/day", $day, "_sample.txt"))),
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+#[cfg(feature = "embed_inputs")]
+embed!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+
+#[cfg(not(feature = "embed_inputs"))]
+fn embedded(_day: u8, _use_sample: bool) -> Option<&'static str> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_and_directory_agree() {
+        let path = inputs_dir().join("day1_sample.txt");
+        let from_path = InputSource::from_arg(path.to_str().unwrap())
+            .read(1, false)
+            .unwrap();
+        let from_directory = InputSource::Directory.read(1, true).unwrap();
+        assert_eq!(from_path, from_directory);
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        let text = InputSource::Text(from_path.clone());
+        assert_eq!(text.read(2, true).unwrap(), from_path);
+        assert!(!text.is_per_day());
+    }
+
+    #[cfg(feature = "embed_inputs")]
+    #[test]
+    fn embedded_matches_directory() {
+        let embedded = InputSource::Embedded.read(3, true).unwrap();
+        assert_eq!(embedded, InputSource::Directory.read(3, true).unwrap());
+        assert!(InputSource::Embedded.read(25, false).is_err());
+    }
+}
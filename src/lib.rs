@@ -1,38 +1,215 @@
-use std::fs::read_to_string;
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
 
 use thiserror::Error;
 
+use cli::Options;
+
+mod answers;
+pub mod bench;
+pub mod cli;
+pub mod days;
+pub mod graph;
+pub mod grid;
+pub mod image;
+mod input;
+mod parse;
+mod report;
+mod timing;
+
+pub use answers::{Answers, Verdict};
+pub use input::{inputs_dir, InputSource, INPUTS_DIR_VAR};
+pub use parse::{input_lines, InputLine};
+pub use report::{Answer, DayReport, Detail, Detailed, Details, Format, PartReport};
+pub use timing::{timed, Timing};
+
 #[derive(Debug, Error)]
 pub enum AdventError {
     #[error("Invalid data")]
     InvalidData,
+    #[error("Day {day} line {line} column {column}: expected {expected}, found '{found}'")]
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        found: String,
+        expected: String,
+    },
+    #[error("Day {day} board {board}: {problem}")]
+    InvalidBoard {
+        day: u8,
+        /// From 1, like the lines of [`AdventError::Parse`].
+        board: usize,
+        problem: String,
+    },
+    #[error("Overflow: {0}")]
+    Overflow(String),
+    #[error("There is no solution for day {0}")]
+    UnknownDay(u8),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("No input available for day {0}")]
+    MissingInput(u8),
+    #[error("{0} answer(s) did not match the expected ones")]
+    WrongAnswers(usize),
     #[error(transparent)]
     IoError(#[from] std::io::Error),
     #[error(transparent)]
     IntParseError(#[from] std::num::ParseIntError),
     #[error(transparent)]
     Utf8Error(#[from] std::string::FromUtf8Error),
-
 }
 
 pub type AdventResult<T> = Result<T, AdventError>;
 
-pub fn read_input(day: u8, use_sample: bool) -> AdventResult<String> {
-    let path = if use_sample {
-        format!("inputs/day{}_sample.txt", day)
-    } else {
-        format!("inputs/day{}.txt", day)
+/// Puzzle part, every day has two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "Part 1"),
+            Part::Two => write!(f, "Part 2"),
+        }
+    }
+}
+
+/// How much the solutions tell about their work, set with `-v` or `-vv`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    #[default]
+    Quiet,
+    /// Summaries of the steps, e.g. the state after each day.
+    Info,
+    /// Everything, e.g. every path found or the grids along the way.
+    Trace,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Whether messages of the given level are printed.
+pub fn log_enabled(level: Verbosity) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Prints to stderr with `-v`, the arguments are only formatted when printed.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log_enabled($crate::Verbosity::Info) {
+            eprintln!($($arg)*);
+        }
     };
-    Ok(read_to_string(path)?)
 }
 
+/// Prints to stderr with `-vv`, the arguments are only formatted when printed.
 #[macro_export]
-macro_rules! timed_run {
-    ( $prefix:literal, $expression:expr ) => {{
-        let start = std::time::Instant::now();
-        let duration = start.elapsed();
-        let result = $expression;
-        println!("{} took ({:?})", $prefix, duration);
-        result
-    }};
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log_enabled($crate::Verbosity::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Common shape of every day solution.
+///
+/// `parse` turns the raw input into the day's own representation, which is then shared
+/// by both parts. The input may borrow from the raw text, hence the lifetime.
+pub trait Solution {
+    /// Day of the advent calendar.
+    const DAY: u8;
+
+    type Input<'a>;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(input: &str) -> AdventResult<Self::Input<'_>>;
+    fn part_one(input: &Self::Input<'_>) -> AdventResult<Self::Answer1>;
+    fn part_two(input: &Self::Input<'_>) -> AdventResult<Self::Answer2>;
+
+    /// Runs either part, with the answer as text so both can be handled alike.
+    fn solve(input: &Self::Input<'_>, part: Part) -> AdventResult<String> {
+        match part {
+            Part::One => Ok(Self::part_one(input)?.to_string()),
+            Part::Two => Ok(Self::part_two(input)?.to_string()),
+        }
+    }
+
+    /// Like [`solve`](Solution::solve), also returning the answer details.
+    fn solve_detailed(input: &Self::Input<'_>, part: Part) -> AdventResult<(String, Details)> {
+        match part {
+            Part::One => {
+                let answer = Self::part_one(input)?;
+                Ok((answer.to_string(), answer.details()))
+            }
+            Part::Two => {
+                let answer = Self::part_two(input)?;
+                Ok((answer.to_string(), answer.details()))
+            }
+        }
+    }
+
+    /// Other implementations of the parts, only used to compare them in the benchmarks.
+    fn variants() -> Vec<Variant<Self>> {
+        Vec::new()
+    }
+}
+
+/// Another implementation of a part, e.g. the brute force one.
+pub struct Variant<S: Solution + ?Sized> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: for<'a, 'b> fn(&'b S::Input<'a>) -> AdventResult<String>,
+}
+
+/// Reads the day input from the inputs directory, see [`InputSource::Directory`].
+pub fn read_input(day: u8, use_sample: bool) -> AdventResult<String> {
+    InputSource::Directory.read(day, use_sample)
+}
+
+/// Reads the input for the solution day, parses it and runs the requested parts.
+///
+/// The answers are checked against the expected ones, unless the input was given as a
+/// single file or stdin, as there is no telling what it is.
+pub fn run<S: Solution>(options: &Options, answers: &Answers) -> AdventResult<DayReport> {
+    let input = options.input.read(S::DAY, options.use_sample)?;
+    let (data, parse) = timed("Parse", || S::parse(&input));
+    let data = data?;
+    let mut parts = vec![];
+    for part in [Part::One, Part::Two] {
+        if options.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        let (solved, timing) = timed(part.to_string(), || S::solve_detailed(&data, part));
+        let (answer, details) = solved?;
+        let verdict = if options.input.is_per_day() {
+            answers.verify(S::DAY, options.use_sample, part, &answer)
+        } else {
+            Verdict::Unknown
+        };
+        parts.push(PartReport {
+            part,
+            answer,
+            details,
+            timing,
+            verdict,
+        });
+    }
+    let solve = parts.iter().map(|p| p.timing.wall).sum();
+    Ok(DayReport {
+        day: S::DAY,
+        parts,
+        timing: Timing::with_phases(format!("Day {}", S::DAY), parse.wall, solve),
+    })
 }
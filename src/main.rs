@@ -0,0 +1,3 @@
+fn main() -> advent::AdventResult<()> {
+    advent::cli::main()
+}
@@ -0,0 +1,86 @@
+//! Helpers for the input parsers to report where the input is malformed.
+
+use std::str::FromStr;
+
+use crate::{AdventError, AdventResult};
+
+/// A line of the puzzle input, it keeps its position to report parse errors.
+#[derive(Debug, Clone, Copy)]
+pub struct InputLine<'a> {
+    pub day: u8,
+    /// Line number, starting at 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    /// Error for `token`, which should be a slice of the line text to get the right column.
+    pub fn error(&self, token: &str, expected: impl Into<String>) -> AdventError {
+        AdventError::Parse {
+            day: self.day,
+            line: self.number,
+            column: self.column(token),
+            found: token.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error for a token missing at the end of the line.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> AdventError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> AdventResult<T> {
+        token
+            .trim()
+            .parse::<T>()
+            .map_err(|_| self.error(token, expected))
+    }
+
+    /// Column of the token in the line, starting at 1.
+    fn column(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        if offset <= self.text.len() {
+            offset + 1
+        } else {
+            self.text.find(token).map_or(1, |offset| offset + 1)
+        }
+    }
+}
+
+/// Numbered lines of the day input.
+pub fn input_lines(day: u8, input: &str) -> impl Iterator<Item = InputLine<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(index, text)| InputLine {
+            day,
+            number: index + 1,
+            text,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_line_and_column() {
+        let line = input_lines(5, "0,9 -> 5,9\n8,0 -> x,8").nth(1).unwrap();
+        let token = line
+            .text
+            .split(" -> ")
+            .nth(1)
+            .unwrap()
+            .split(',')
+            .next()
+            .unwrap();
+        let error = line.parse::<u32>(token, "a coordinate").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Day 5 line 2 column 8: expected a coordinate, found 'x'"
+        );
+        let error = line.error_at_end("'|'");
+        assert!(matches!(error, AdventError::Parse { column: 11, .. }));
+    }
+}
@@ -0,0 +1,329 @@
+//! What running the solutions produced, and how to print it.
+
+use std::fmt::{Display, Write};
+
+use crate::{answers::Verdict, AdventError, AdventResult, Part, Timing};
+
+/// A value computed along the answer, e.g. the best position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Detail {
+    Int(i128),
+    Text(String),
+    List(Vec<i128>),
+}
+
+macro_rules! detail_from_int {
+    ( $( $int:ty ),* ) => {
+        $(
+            impl From<$int> for Detail {
+                fn from(value: $int) -> Self {
+                    Detail::Int(value as i128)
+                }
+            }
+
+            impl From<Vec<$int>> for Detail {
+                fn from(values: Vec<$int>) -> Self {
+                    Detail::List(values.into_iter().map(|v| v as i128).collect())
+                }
+            }
+        )*
+    };
+}
+
+detail_from_int!(i32, i64, u32, u64, usize);
+
+impl From<&str> for Detail {
+    fn from(value: &str) -> Self {
+        Detail::Text(value.to_owned())
+    }
+}
+
+impl Display for Detail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Detail::Int(value) => write!(f, "{}", value),
+            Detail::Text(value) => write!(f, "{}", value),
+            Detail::List(values) => {
+                let values: Vec<String> = values.iter().map(i128::to_string).collect();
+                write!(f, "{}", values.join(" "))
+            }
+        }
+    }
+}
+
+pub type Details = Vec<(&'static str, Detail)>;
+
+/// A part answer. `Display` must show only the answer itself, as it is what gets checked.
+pub trait Answer: Display {
+    /// Other values worth reporting with the answer.
+    fn details(&self) -> Details {
+        Vec::new()
+    }
+}
+
+macro_rules! plain_answer {
+    ( $( $answer:ty ),* ) => {
+        $( impl Answer for $answer {} )*
+    };
+}
+
+plain_answer!(i32, i64, u32, u64, u128, usize, String);
+
+/// An answer with some details.
+#[derive(Debug, Clone)]
+pub struct Detailed<T> {
+    pub answer: T,
+    pub details: Details,
+}
+
+impl<T> Detailed<T> {
+    pub fn new(answer: T) -> Self {
+        Self {
+            answer,
+            details: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Into<Detail>) -> Self {
+        self.details.push((name, value.into()));
+        self
+    }
+}
+
+impl<T: Display> Display for Detailed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.answer.fmt(f)
+    }
+}
+
+impl<T: Display> Answer for Detailed<T> {
+    fn details(&self) -> Details {
+        self.details.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: String,
+    pub details: Details,
+    pub timing: Timing,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub parts: Vec<PartReport>,
+    /// The whole day, with the parse and solve phases apart.
+    pub timing: Timing,
+}
+
+impl DayReport {
+    /// Number of parts with the given verdict.
+    pub fn count(&self, verdict: fn(&Verdict) -> bool) -> usize {
+        self.parts.iter().filter(|p| verdict(&p.verdict)).count()
+    }
+
+    /// The answers with their verdict, followed by the details as `name=value` pairs.
+    pub fn text(&self) -> String {
+        let mut text = format!("Advent of Code 2021! Rust edition. Day {}\n", self.day);
+        for part in &self.parts {
+            text += &format!(
+                "{}\n{}: {} ({})",
+                part.timing, part.part, part.answer, part.verdict
+            );
+            let details: Vec<String> = part
+                .details
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            if !details.is_empty() {
+                text += &format!(" {}", details.join(", "));
+            }
+            text.push('\n');
+        }
+        text += &format!("{}\n", self.timing);
+        text
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> AdventResult<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(AdventError::InvalidArgument(format!(
+                "unknown format '{}', expected json, csv or text",
+                s
+            ))),
+        }
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Fail { .. } => "fail",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut result = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_detail(detail: &Detail) -> String {
+    match detail {
+        Detail::Int(value) => value.to_string(),
+        Detail::Text(value) => json_string(value),
+        Detail::List(values) => {
+            let values: Vec<String> = values.iter().map(i128::to_string).collect();
+            format!("[{}]", values.join(","))
+        }
+    }
+}
+
+/// One JSON object per part, in an array.
+pub fn json(reports: &[DayReport]) -> String {
+    let mut records = vec![];
+    for report in reports {
+        for part in &report.parts {
+            let details: Vec<String> = part
+                .details
+                .iter()
+                .map(|(name, value)| format!("{}:{}", json_string(name), json_detail(value)))
+                .collect();
+            let expected = match &part.verdict {
+                Verdict::Fail { expected } => format!(",\"expected\":{}", json_string(expected)),
+                _ => String::new(),
+            };
+            records.push(format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"details\":{{{}}},\"verdict\":\"{}\"{},\"parse_ns\":{},\"solve_ns\":{}}}",
+                report.day,
+                part_number(part.part),
+                json_string(&part.answer),
+                details.join(","),
+                verdict_name(&part.verdict),
+                expected,
+                report.timing.parse.unwrap_or_default().as_nanos(),
+                part.timing.wall.as_nanos(),
+            ));
+        }
+    }
+    format!("[\n{}\n]", records.join(",\n"))
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+/// One row per part, the details as `name=value` pairs separated by `;`.
+pub fn csv(reports: &[DayReport]) -> String {
+    let mut text = String::from("day,part,answer,details,verdict,parse_ns,solve_ns\n");
+    for report in reports {
+        for part in &report.parts {
+            let details: Vec<String> = part
+                .details
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            text += &format!(
+                "{},{},{},{},{},{},{}\n",
+                report.day,
+                part_number(part.part),
+                csv_field(&part.answer),
+                csv_field(&details.join(";")),
+                verdict_name(&part.verdict),
+                report.timing.parse.unwrap_or_default().as_nanos(),
+                part.timing.wall.as_nanos(),
+            );
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn report() -> DayReport {
+        let us = Duration::from_micros;
+        DayReport {
+            day: 7,
+            parts: vec![PartReport {
+                part: Part::One,
+                answer: "a \"b\"\nc".to_owned(),
+                details: Detailed::new(37)
+                    .with("best_position", 2)
+                    .with("sizes", vec![1_u32, 2])
+                    .details(),
+                timing: Timing::new("Part 1", us(2)),
+                verdict: Verdict::Fail {
+                    expected: "37".to_owned(),
+                },
+            }],
+            timing: Timing::with_phases("Day 7", us(1), us(2)),
+        }
+    }
+
+    #[test]
+    fn json_records() {
+        assert_eq!(
+            json(&[report()]),
+            "[\n{\"day\":7,\"part\":1,\"answer\":\"a \\\"b\\\"\\nc\",\"details\":{\"best_position\":2,\"sizes\":[1,2]},\"verdict\":\"fail\",\"expected\":\"37\",\"parse_ns\":1000,\"solve_ns\":2000}\n]"
+        );
+    }
+
+    #[test]
+    fn text_details() {
+        let text = report().text();
+        let line = text.lines().find(|l| l.starts_with("c (")).unwrap();
+        assert!(line.ends_with(") best_position=2, sizes=1 2"), "{}", line);
+    }
+
+    #[test]
+    fn csv_records() {
+        assert_eq!(
+            csv(&[report()]),
+            "day,part,answer,details,verdict,parse_ns,solve_ns\n7,1,\"a \"\"b\"\"\nc\",best_position=2;sizes=1 2,fail,1000,2000\n"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Timing reports for the solutions.
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// How long something took.
+///
+/// `wall` is always the full elapsed time, when the parse and solve phases were measured
+/// separately they are also kept, `wall` being their sum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub label: String,
+    pub wall: Duration,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+}
+
+impl Timing {
+    pub fn new(label: impl Into<String>, wall: Duration) -> Self {
+        Self {
+            label: label.into(),
+            wall,
+            parse: None,
+            solve: None,
+        }
+    }
+
+    pub fn with_phases(label: impl Into<String>, parse: Duration, solve: Duration) -> Self {
+        Self {
+            label: label.into(),
+            wall: parse + solve,
+            parse: Some(parse),
+            solve: Some(solve),
+        }
+    }
+
+    /// Adds up the timings, phases are only kept if every timing has them.
+    pub fn total<'a, I: IntoIterator<Item = &'a Timing>>(
+        label: impl Into<String>,
+        timings: I,
+    ) -> Self {
+        let mut total = Self::with_phases(label, Duration::ZERO, Duration::ZERO);
+        for timing in timings {
+            total.wall += timing.wall;
+            total.parse = total.parse.zip(timing.parse).map(|(a, b)| a + b);
+            total.solve = total.solve.zip(timing.solve).map(|(a, b)| a + b);
+        }
+        total
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} took ({:?}", self.label, self.wall)?;
+        if let (Some(parse), Some(solve)) = (self.parse, self.solve) {
+            write!(f, ": parse {:?}, solve {:?}", parse, solve)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Runs `f` and measures how long it took.
+pub fn timed<T>(label: impl Into<String>, f: impl FnOnce() -> T) -> (T, Timing) {
+    let start = Instant::now();
+    let result = f();
+    let duration = start.elapsed();
+    (result, Timing::new(label, duration))
+}
+
+/// Evaluates the expression and prints how long it took.
+///
+/// Unlike [`timed`] the expression is not wrapped in a closure, so it can use `?`.
+#[macro_export]
+macro_rules! timed_run {
+    ( $prefix:literal, $expression:expr ) => {{
+        let start = std::time::Instant::now();
+        let result = $expression;
+        let duration = start.elapsed();
+        println!("{}", $crate::Timing::new($prefix, duration));
+        result
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_the_expression() {
+        let pause = Duration::from_millis(10);
+        let (value, timing) = timed("Sleep", || {
+            std::thread::sleep(pause);
+            42
+        });
+        assert_eq!(value, 42);
+        assert!(timing.wall >= pause);
+
+        let value = timed_run!("Sleep", {
+            std::thread::sleep(pause);
+            42
+        });
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn total_keeps_phases() {
+        let ms = Duration::from_millis;
+        let timings = [
+            Timing::with_phases("Part 1", ms(1), ms(2)),
+            Timing::with_phases("Part 2", ms(1), ms(4)),
+        ];
+        let total = Timing::total("Day", &timings);
+        assert_eq!(total.wall, ms(8));
+        assert_eq!(total.parse, Some(ms(2)));
+        assert_eq!(total.solve, Some(ms(6)));
+
+        let total = Timing::total("All", [&total, &Timing::new("Other", ms(2))]);
+        assert_eq!(total.wall, ms(10));
+        assert_eq!(total.parse, None);
+    }
+}
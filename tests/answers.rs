@@ -0,0 +1,71 @@
+//! One test per day, part and input, checked against `answers.toml`.
+
+use advent::{read_input, Answers, Part, Solution, Verdict};
+
+/// Solves the part and compares it with the expected answer, which must be there.
+fn check<S: Solution>(use_sample: bool, part: Part) {
+    let answers = Answers::load().expect("Invalid answers file");
+    assert!(
+        answers.expected(S::DAY, use_sample, part).is_some(),
+        "no answer in answers.toml for day {} {} {}",
+        S::DAY,
+        if use_sample { "sample" } else { "input" },
+        part
+    );
+    let input = read_input(S::DAY, use_sample).expect("Error reading input");
+    let data = S::parse(&input).expect("Invalid data");
+    let answer = S::solve(&data, part).expect("No answer");
+    assert_eq!(
+        answers.verify(S::DAY, use_sample, part, &answer),
+        Verdict::Pass,
+        "got {}",
+        answer
+    );
+}
+
+macro_rules! answer_tests {
+    ( $( $day:ident: $solution:ty ),* $(,)? ) => {
+        $(
+            mod $day {
+                use super::*;
+
+                #[test]
+                fn sample_part1() {
+                    check::<$solution>(true, Part::One);
+                }
+
+                #[test]
+                fn sample_part2() {
+                    check::<$solution>(true, Part::Two);
+                }
+
+                #[test]
+                fn input_part1() {
+                    check::<$solution>(false, Part::One);
+                }
+
+                #[test]
+                fn input_part2() {
+                    check::<$solution>(false, Part::Two);
+                }
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day1: advent::days::day1::Day1,
+    day2: advent::days::day2::Day2,
+    day3: advent::days::day3::Day3,
+    day4: advent::days::day4::Day4,
+    day5: advent::days::day5::Day5,
+    day6: advent::days::day6::Day6,
+    day7: advent::days::day7::Day7,
+    day8: advent::days::day8::Day8,
+    day9: advent::days::day9::Day9,
+    day10: advent::days::day10::Day10,
+    day11: advent::days::day11::Day11,
+    day12: advent::days::day12::Day12,
+    day13: advent::days::day13::Day13,
+    day14: advent::days::day14::Day14,
+}